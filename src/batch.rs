@@ -0,0 +1,213 @@
+use std::{
+    cell::Cell,
+    ffi::CStr,
+    path::PathBuf,
+    rc::Rc,
+    time::{Duration, Instant},
+};
+
+use crate::{
+    callbacks::CallbackHandler, CallbackType, ConvertOptions, ConvertOutput, Office, OfficeError,
+};
+
+/// Single conversion job for [Office::convert_batch]
+#[derive(Debug, Clone)]
+pub struct ConvertJob {
+    /// Path to the input document
+    pub input: PathBuf,
+    /// Path to write the converted document to
+    pub output: PathBuf,
+    /// Options for the conversion
+    pub options: ConvertOptions,
+}
+
+impl ConvertJob {
+    /// Creates a new job converting `input` to `output` with the default options
+    pub fn new<I: Into<PathBuf>, O: Into<PathBuf>>(input: I, output: O) -> Self {
+        Self {
+            input: input.into(),
+            output: output.into(),
+            options: ConvertOptions::default(),
+        }
+    }
+
+    /// Sets the options for the conversion
+    pub fn options(mut self, options: ConvertOptions) -> Self {
+        self.options = options;
+        self
+    }
+}
+
+/// Report for a single job converted by [Office::convert_batch]
+#[derive(Debug)]
+pub struct ConversionReport {
+    /// Index of the job within the batch
+    pub index: usize,
+    /// Path to the input document
+    pub input: PathBuf,
+    /// Path the converted document was written to
+    pub output: PathBuf,
+    /// Time taken to load and convert the document
+    pub duration: Duration,
+    /// Outcome of the conversion
+    pub result: Result<ConvertOutput, OfficeError>,
+}
+
+impl ConversionReport {
+    /// Size of the output file in bytes, [None] when the conversion failed
+    pub fn output_size(&self) -> Option<u64> {
+        self.result.as_ref().ok().map(|output| output.size)
+    }
+}
+
+/// Progress of the current operation reported through the status
+/// indicator callbacks
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Progress {
+    /// Operation started ([CallbackType::StatusIndicatorStart]) with
+    /// an optional description
+    Start { text: String },
+    /// Operation progressed ([CallbackType::StatusIndicatorSetValue])
+    /// to the provided percentage
+    Value { percent: u8 },
+    /// Operation finished ([CallbackType::StatusIndicatorFinish])
+    Finish,
+}
+
+impl Progress {
+    /// Creates a progress event from a callback type and its payload,
+    /// [None] for callbacks that do not report progress
+    pub fn from_callback(ty: CallbackType, payload: &str) -> Option<Progress> {
+        match ty {
+            CallbackType::StatusIndicatorStart => Some(Progress::Start {
+                text: payload.to_string(),
+            }),
+            CallbackType::StatusIndicatorSetValue => {
+                let percent: u32 = payload.trim().parse().ok()?;
+                Some(Progress::Value {
+                    percent: percent.min(100) as u8,
+                })
+            }
+            CallbackType::StatusIndicatorFinish => Some(Progress::Finish),
+            _ => None,
+        }
+    }
+}
+
+/// Iterator converting the jobs of [Office::convert_batch], each job is
+/// converted when the next report is requested
+pub struct ConvertBatch<'a, I> {
+    /// Office instance used for conversion
+    office: &'a Office,
+    /// Remaining jobs
+    jobs: std::iter::Enumerate<I>,
+    /// Index of the job currently being converted, shared with the callback
+    current: Rc<Cell<usize>>,
+    /// Progress callback handler, removed once the batch is dropped
+    _progress: CallbackHandler,
+}
+
+impl<I> Iterator for ConvertBatch<'_, I>
+where
+    I: Iterator<Item = ConvertJob>,
+{
+    type Item = ConversionReport;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (index, job) = self.jobs.next()?;
+        self.current.set(index);
+
+        let start = Instant::now();
+        let result = self.office.convert(&job.input, &job.output, job.options);
+        let duration = start.elapsed();
+
+        Some(ConversionReport {
+            index,
+            input: job.input,
+            output: job.output,
+            duration,
+            result,
+        })
+    }
+}
+
+impl Office {
+    /// Converts a batch of `jobs` returning an iterator that converts each
+    /// job in turn, producing a [ConversionReport] for every job
+    ///
+    /// `on_progress` is invoked with the index of the job and the [Progress]
+    /// whenever LibreOffice reports progress while loading or saving
+    ///
+    /// Progress is reported alongside any callback registered with
+    /// [Office::register_callback] until the returned iterator is dropped
+    pub fn convert_batch<I, F>(
+        &self,
+        jobs: I,
+        mut on_progress: F,
+    ) -> Result<ConvertBatch<'_, I::IntoIter>, OfficeError>
+    where
+        I: IntoIterator<Item = ConvertJob>,
+        F: FnMut(usize, Progress) + 'static,
+    {
+        let current = Rc::new(Cell::new(0));
+
+        let progress = self.add_callback_handler({
+            let current = current.clone();
+
+            move |_, ty, payload| {
+                let payload = if payload.is_null() {
+                    String::new()
+                } else {
+                    unsafe { CStr::from_ptr(payload) }
+                        .to_string_lossy()
+                        .into_owned()
+                };
+
+                if let Some(progress) = Progress::from_callback(ty, &payload) {
+                    on_progress(current.get(), progress);
+                }
+            }
+        })?;
+
+        Ok(ConvertBatch {
+            office: self,
+            jobs: jobs.into_iter().enumerate(),
+            current,
+            _progress: progress,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Progress;
+    use crate::CallbackType;
+
+    /// Tests status indicator callbacks are parsed into progress events
+    #[test]
+    fn test_progress_from_callback() {
+        assert_eq!(
+            Progress::from_callback(CallbackType::StatusIndicatorStart, "Loading"),
+            Some(Progress::Start {
+                text: "Loading".to_string()
+            })
+        );
+        assert_eq!(
+            Progress::from_callback(CallbackType::StatusIndicatorSetValue, "42"),
+            Some(Progress::Value { percent: 42 })
+        );
+        assert_eq!(
+            Progress::from_callback(CallbackType::StatusIndicatorSetValue, "300"),
+            Some(Progress::Value { percent: 100 })
+        );
+        assert_eq!(
+            Progress::from_callback(CallbackType::StatusIndicatorSetValue, "invalid"),
+            None
+        );
+        assert_eq!(
+            Progress::from_callback(CallbackType::StatusIndicatorFinish, ""),
+            Some(Progress::Finish)
+        );
+        assert_eq!(Progress::from_callback(CallbackType::Error, ""), None);
+    }
+}
@@ -202,4 +202,60 @@ pub struct LibreOfficeKitDocumentClass {
     pub getDocumentType: ::std::option::Option<
         unsafe extern "C" fn(pThis: *mut LibreOfficeKitDocument) -> ::std::os::raw::c_int,
     >,
+    pub getParts: ::std::option::Option<
+        unsafe extern "C" fn(pThis: *mut LibreOfficeKitDocument) -> ::std::os::raw::c_int,
+    >,
+    pub getPartPageRectangles: ::std::option::Option<
+        unsafe extern "C" fn(pThis: *mut LibreOfficeKitDocument) -> *mut ::std::os::raw::c_char,
+    >,
+    pub getPart: ::std::option::Option<
+        unsafe extern "C" fn(pThis: *mut LibreOfficeKitDocument) -> ::std::os::raw::c_int,
+    >,
+    pub setPart: ::std::option::Option<
+        unsafe extern "C" fn(pThis: *mut LibreOfficeKitDocument, nPart: ::std::os::raw::c_int),
+    >,
+    pub getPartName: ::std::option::Option<
+        unsafe extern "C" fn(
+            pThis: *mut LibreOfficeKitDocument,
+            nPart: ::std::os::raw::c_int,
+        ) -> *mut ::std::os::raw::c_char,
+    >,
+    pub setPartMode: ::std::option::Option<
+        unsafe extern "C" fn(pThis: *mut LibreOfficeKitDocument, nMode: ::std::os::raw::c_int),
+    >,
+    pub paintTile: ::std::option::Option<
+        unsafe extern "C" fn(
+            pThis: *mut LibreOfficeKitDocument,
+            pBuffer: *mut ::std::os::raw::c_uchar,
+            nCanvasWidth: ::std::os::raw::c_int,
+            nCanvasHeight: ::std::os::raw::c_int,
+            nTilePosX: ::std::os::raw::c_int,
+            nTilePosY: ::std::os::raw::c_int,
+            nTileWidth: ::std::os::raw::c_int,
+            nTileHeight: ::std::os::raw::c_int,
+        ),
+    >,
+    pub getTileMode: ::std::option::Option<
+        unsafe extern "C" fn(pThis: *mut LibreOfficeKitDocument) -> ::std::os::raw::c_int,
+    >,
+    pub getDocumentSize: ::std::option::Option<
+        unsafe extern "C" fn(
+            pThis: *mut LibreOfficeKitDocument,
+            pWidth: *mut ::std::os::raw::c_long,
+            pHeight: *mut ::std::os::raw::c_long,
+        ),
+    >,
+    pub initializeForRendering: ::std::option::Option<
+        unsafe extern "C" fn(
+            pThis: *mut LibreOfficeKitDocument,
+            pArguments: *const ::std::os::raw::c_char,
+        ),
+    >,
+    pub registerCallback: ::std::option::Option<
+        unsafe extern "C" fn(
+            pThis: *mut LibreOfficeKitDocument,
+            pCallback: LibreOfficeKitCallback,
+            pData: *mut ::std::os::raw::c_void,
+        ),
+    >,
 }
@@ -0,0 +1,227 @@
+//! Dispatching of the single office callback to the callback registered
+//! with [Office::register_callback] and the handlers used internally
+
+use std::{
+    cell::{Cell, RefCell},
+    ffi::CStr,
+    os::raw::c_char,
+    rc::{Rc, Weak},
+};
+
+use num_enum::FromPrimitive;
+
+use crate::{sys, trace, CallbackOffice, CallbackType, Office, OfficeError};
+
+/// Callback function invoked with office events
+type Callback = Rc<RefCell<dyn FnMut(CallbackOffice, CallbackType, *const c_char)>>;
+
+/// Office only supports a single callback, the dispatcher is registered as
+/// that callback and forwards events to the user callback and to handlers
+/// added for the duration of an operation
+#[derive(Default)]
+pub(crate) struct CallbackDispatcher {
+    /// Whether the dispatcher is registered with office
+    installed: Cell<bool>,
+    /// Callback registered with [Office::register_callback]
+    user: RefCell<Option<Callback>>,
+    /// Handlers added with [Office::add_callback_handler]
+    handlers: RefCell<Vec<(u64, Callback)>>,
+    /// ID for the next added handler
+    next_handler_id: Cell<u64>,
+    /// Number of events currently being dispatched
+    dispatching: Cell<u32>,
+}
+
+impl CallbackDispatcher {
+    /// Registers the dispatcher as the office callback if not already registered
+    pub(crate) fn install(self: &Rc<Self>, raw: &Rc<sys::OfficeRaw>) -> Result<(), OfficeError> {
+        if self.installed.get() {
+            return Ok(());
+        }
+
+        let callbacks = Rc::downgrade(self);
+        let office = Rc::downgrade(raw);
+
+        unsafe {
+            raw.register_callback(move |ty, payload| {
+                if let Some(callbacks) = callbacks.upgrade() {
+                    let callback_office = CallbackOffice {
+                        raw: office.clone(),
+                        callbacks: Rc::downgrade(&callbacks),
+                    };
+
+                    callbacks.dispatch(callback_office, CallbackType::from_primitive(ty), payload);
+                }
+            })?;
+        }
+
+        self.installed.set(true);
+        Ok(())
+    }
+
+    /// Unregisters the dispatcher from office when there are no callbacks
+    /// or handlers left to dispatch to
+    ///
+    /// The dispatcher stays registered when released while dispatching, as
+    /// unregistering would free the running callback
+    pub(crate) fn release(&self, raw: &sys::OfficeRaw) -> Result<(), OfficeError> {
+        if !self.installed.get()
+            || self.dispatching.get() > 0
+            || self.user.borrow().is_some()
+            || !self.handlers.borrow().is_empty()
+        {
+            return Ok(());
+        }
+
+        // Core logs are always routed into tracing, active traces receive events
+        if cfg!(feature = "tracing") || trace::is_active() {
+            return Ok(());
+        }
+
+        unsafe { raw.clear_callback()? };
+
+        self.installed.set(false);
+        Ok(())
+    }
+
+    /// Sets the user callback, [None] to clear it
+    pub(crate) fn set_user(&self, callback: Option<Callback>) {
+        *self.user.borrow_mut() = callback;
+    }
+
+    /// Forwards an event to the built-in routing, handlers and user callback
+    fn dispatch(&self, office: CallbackOffice, ty: CallbackType, payload: *const c_char) {
+        // Route core logs into tracing
+        #[cfg(feature = "tracing")]
+        if let (CallbackType::CoreLog, false) = (ty, payload.is_null()) {
+            let payload = unsafe { CStr::from_ptr(payload) }.to_string_lossy();
+            crate::logging::emit_core_log(&payload);
+        }
+
+        // Collect trace events for an active trace
+        if let (CallbackType::ProfileFrame, false) = (ty, payload.is_null()) {
+            let payload = unsafe { CStr::from_ptr(payload) }.to_string_lossy();
+            trace::record_frame(&payload);
+        }
+
+        // Callbacks are cloned out so they can add or remove callbacks while running
+        let handlers: Vec<Callback> = self
+            .handlers
+            .borrow()
+            .iter()
+            .map(|(_, handler)| handler.clone())
+            .collect();
+        let user = self.user.borrow().clone();
+
+        self.dispatching.set(self.dispatching.get() + 1);
+
+        for callback in handlers.iter().chain(user.iter()) {
+            // Events raised by a callback while it is running are not delivered to it
+            if let Ok(mut callback) = callback.try_borrow_mut() {
+                (*callback)(office.clone(), ty, payload);
+            }
+        }
+
+        self.dispatching.set(self.dispatching.get() - 1);
+    }
+}
+
+/// Handler added with [Office::add_callback_handler], the handler is
+/// removed when this is dropped and the dispatcher is released from office
+/// once nothing is left to dispatch to
+pub(crate) struct CallbackHandler {
+    office: Weak<sys::OfficeRaw>,
+    callbacks: Weak<CallbackDispatcher>,
+    id: u64,
+}
+
+impl Drop for CallbackHandler {
+    fn drop(&mut self) {
+        let Some(callbacks) = self.callbacks.upgrade() else {
+            return;
+        };
+
+        callbacks
+            .handlers
+            .borrow_mut()
+            .retain(|(id, _)| *id != self.id);
+
+        if let Some(office) = self.office.upgrade() {
+            _ = callbacks.release(&office);
+        }
+    }
+}
+
+impl Office {
+    /// Adds a `handler` receiving office events alongside the callback
+    /// registered with [Office::register_callback]
+    pub(crate) fn add_callback_handler<F>(&self, handler: F) -> Result<CallbackHandler, OfficeError>
+    where
+        F: FnMut(CallbackOffice, CallbackType, *const c_char) + 'static,
+    {
+        self.callbacks.install(&self.raw)?;
+
+        let id = self.callbacks.next_handler_id.get();
+        self.callbacks.next_handler_id.set(id + 1);
+        self.callbacks
+            .handlers
+            .borrow_mut()
+            .push((id, Rc::new(RefCell::new(handler))));
+
+        Ok(CallbackHandler {
+            office: Rc::downgrade(&self.raw),
+            callbacks: Rc::downgrade(&self.callbacks),
+            id,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::{
+        cell::RefCell,
+        ptr::null,
+        rc::{Rc, Weak},
+    };
+
+    use super::{CallbackDispatcher, CallbackHandler};
+    use crate::{CallbackOffice, CallbackType};
+
+    /// Tests events are delivered to both handlers and the user callback
+    #[test]
+    fn test_dispatch() {
+        let callbacks = Rc::new(CallbackDispatcher::default());
+        let events: Rc<RefCell<Vec<&str>>> = Default::default();
+
+        callbacks.set_user(Some(Rc::new(RefCell::new({
+            let events = events.clone();
+            move |_, _, _| events.borrow_mut().push("user")
+        }))));
+
+        callbacks.handlers.borrow_mut().push((
+            0,
+            Rc::new(RefCell::new({
+                let events = events.clone();
+                move |_, _, _| events.borrow_mut().push("handler")
+            })),
+        ));
+
+        let office = CallbackOffice {
+            raw: Weak::new(),
+            callbacks: Rc::downgrade(&callbacks),
+        };
+
+        callbacks.dispatch(office.clone(), CallbackType::Error, null());
+        assert_eq!(*events.borrow(), vec!["handler", "user"]);
+
+        // Dropping the handler leaves the user callback in place
+        drop(CallbackHandler {
+            office: Weak::new(),
+            callbacks: Rc::downgrade(&callbacks),
+            id: 0,
+        });
+
+        callbacks.dispatch(office, CallbackType::Error, null());
+        assert_eq!(*events.borrow(), vec!["handler", "user", "user"]);
+    }
+}
@@ -0,0 +1,407 @@
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+    time::SystemTime,
+};
+
+use crate::{
+    CsvFilterOptions, CsvSheet, DocUrl, Document, DocumentType, LoadOptions, Office, OfficeError,
+};
+
+/// Options for converting a document using [Office::convert]
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct ConvertOptions {
+    /// Explicit output format, inferred from the output extension when not set
+    format: Option<String>,
+    /// Options provided to the export filter
+    filter_options: Option<String>,
+    /// Options used when loading the input document
+    load_options: Option<LoadOptions>,
+    /// Whether the conversion fails when the document uses missing fonts
+    strict_fonts: bool,
+}
+
+impl ConvertOptions {
+    /// Creates a new set of default convert options
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the output format (i.e "pdf"), by default this is inferred
+    /// from the extension of the output path
+    pub fn format<S: Into<String>>(mut self, format: S) -> Self {
+        self.format = Some(format.into());
+        self
+    }
+
+    /// Sets the options provided to the export filter
+    pub fn filter_options<S: Into<String>>(mut self, filter_options: S) -> Self {
+        self.filter_options = Some(filter_options.into());
+        self
+    }
+
+    /// Sets the options used when loading the input document
+    pub fn load_options(mut self, load_options: LoadOptions) -> Self {
+        self.load_options = Some(load_options);
+        self
+    }
+
+    /// Sets whether the conversion fails with [OfficeError::FontsMissing] when
+    /// the document uses fonts that are not available, by default missing
+    /// fonts are substituted and listed in [ConvertOutput::missing_fonts]
+    pub fn strict_fonts(mut self, strict_fonts: bool) -> Self {
+        self.strict_fonts = strict_fonts;
+        self
+    }
+}
+
+/// Details about a successful conversion from [Office::convert]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConvertOutput {
+    /// Type of the input document
+    pub document_type: DocumentType,
+    /// Format the document was saved as (i.e "pdf")
+    pub format: String,
+    /// Size of the output file in bytes
+    pub size: u64,
+    /// Fonts used by the document that were not available and have been
+    /// substituted, see [Document::missing_fonts]
+    pub missing_fonts: Vec<String>,
+}
+
+/// Hint for the format of input provided to [Office::convert_bytes]
+///
+/// LibreOffice uses the file extension alongside the content to detect
+/// the format of a document, providing a hint improves detection
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FormatHint {
+    /// File extension of the input (i.e "docx")
+    Extension(String),
+    /// Mime type of the input (i.e "application/pdf")
+    MimeType(String),
+    /// No hint, the format is detected from the content alone
+    None,
+}
+
+impl FormatHint {
+    /// Mapping between common mime types and their file extensions
+    const MIME_EXTENSIONS: &'static [(&'static str, &'static str)] = &[
+        ("application/pdf", "pdf"),
+        ("application/msword", "doc"),
+        (
+            "application/vnd.openxmlformats-officedocument.wordprocessingml.document",
+            "docx",
+        ),
+        ("application/vnd.ms-excel", "xls"),
+        (
+            "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet",
+            "xlsx",
+        ),
+        ("application/vnd.ms-powerpoint", "ppt"),
+        (
+            "application/vnd.openxmlformats-officedocument.presentationml.presentation",
+            "pptx",
+        ),
+        ("application/vnd.oasis.opendocument.text", "odt"),
+        ("application/vnd.oasis.opendocument.spreadsheet", "ods"),
+        ("application/vnd.oasis.opendocument.presentation", "odp"),
+        ("application/vnd.oasis.opendocument.graphics", "odg"),
+        ("application/rtf", "rtf"),
+        ("text/rtf", "rtf"),
+        ("text/plain", "txt"),
+        ("text/csv", "csv"),
+        ("text/html", "html"),
+    ];
+
+    /// Gets the file extension for the hint
+    fn extension(&self) -> Option<String> {
+        let extension = match self {
+            FormatHint::Extension(extension) => extension.trim_start_matches('.'),
+            FormatHint::MimeType(mime) => {
+                let mime = mime.split(';').next().unwrap_or_default().trim();
+
+                Self::MIME_EXTENSIONS
+                    .iter()
+                    .find(|(value, _)| value.eq_ignore_ascii_case(mime))
+                    .map(|(_, extension)| *extension)?
+            }
+            FormatHint::None => return None,
+        };
+
+        // Extension is used within a file name, only allow safe characters
+        if extension.is_empty() || !extension.chars().all(|value| value.is_ascii_alphanumeric()) {
+            return None;
+        }
+
+        Some(extension.to_ascii_lowercase())
+    }
+}
+
+/// Infers the output format from the extension of the provided `path`
+fn infer_format(path: &Path) -> Option<String> {
+    let extension = path.extension()?.to_str()?;
+    Some(extension.to_ascii_lowercase())
+}
+
+/// Converts a path into an absolute [DocUrl]
+fn path_url(path: &Path) -> Result<DocUrl, OfficeError> {
+    let path = path.to_str().ok_or(OfficeError::InvalidPath)?;
+    DocUrl::from_absolute_path(path)
+}
+
+/// Resolves an output path relative to the current directory
+fn output_path(output: &Path) -> Result<PathBuf, OfficeError> {
+    if output.is_absolute() {
+        return Ok(output.to_path_buf());
+    }
+
+    Ok(std::env::current_dir()?.join(output))
+}
+
+/// Collects the files within `directory` that belong to a multi-sheet CSV
+/// export of `stem` (i.e stem-Sheet1.csv) along with their modified time
+fn csv_sheet_files(
+    directory: &Path,
+    stem: &str,
+) -> Result<HashMap<PathBuf, SystemTime>, OfficeError> {
+    let prefix = format!("{stem}-");
+
+    let files = std::fs::read_dir(directory)?
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| {
+            let file_name = entry.file_name();
+            let file_name = file_name.to_str()?;
+
+            if !file_name.starts_with(&prefix) || !file_name.ends_with(".csv") {
+                return None;
+            }
+
+            let modified = entry.metadata().ok()?.modified().ok()?;
+            Some((entry.path(), modified))
+        })
+        .collect();
+
+    Ok(files)
+}
+
+impl Office {
+    /// Converts the document at `input` into the format inferred from the
+    /// extension of `output`, saving the converted document to `output`
+    ///
+    /// Any existing file at `output` will be replaced. The conversion is only
+    /// considered successful when the output file exists and is non-empty
+    ///
+    /// Office picks the export filter for the format from the loaded document
+    /// type (i.e "writer_pdf_Export" or "calc_pdf_Export"), returns
+    /// [OfficeError::UnsupportedExportFormat] when it has no export filter
+    /// for the format
+    pub fn convert(
+        &self,
+        input: &Path,
+        output: &Path,
+        options: ConvertOptions,
+    ) -> Result<ConvertOutput, OfficeError> {
+        let format = match options.format {
+            Some(format) => format,
+            None => infer_format(output)
+                .ok_or_else(|| OfficeError::UnknownOutputFormat(output.to_path_buf()))?,
+        };
+
+        let output = output_path(output)?;
+        let output_url = path_url(&output)?;
+
+        let mut document = self.load_path(input, options.load_options.as_ref())?;
+
+        let document_type = document.get_document_type()?;
+
+        // Remove any existing output so a stale file is not mistaken for the result
+        if output.exists() {
+            std::fs::remove_file(&output)?;
+        }
+
+        // Office picks the export filter for the format and document type
+        document
+            .save_as(&output_url, &format, options.filter_options.as_deref())
+            .map_err(|err| match err {
+                OfficeError::SaveFailed { reason, .. }
+                    if reason.contains("no output filter found") =>
+                {
+                    OfficeError::UnsupportedExportFormat {
+                        document_type,
+                        format: format.clone(),
+                    }
+                }
+                err => err,
+            })?;
+
+        let size = match std::fs::metadata(&output) {
+            Ok(metadata) if metadata.len() > 0 => metadata.len(),
+            _ => return Err(OfficeError::ConversionFailed(output)),
+        };
+
+        let missing_fonts = document.missing_fonts();
+
+        if options.strict_fonts && !missing_fonts.is_empty() {
+            // Output using substituted fonts is not usable
+            std::fs::remove_file(&output)?;
+            return Err(OfficeError::FontsMissing(missing_fonts));
+        }
+
+        Ok(ConvertOutput {
+            document_type,
+            format,
+            size,
+            missing_fonts,
+        })
+    }
+
+    /// Converts the spreadsheet at `input` into CSV exporting every sheet
+    /// to a separate file
+    ///
+    /// Each file is named after `output` with the sheet name appended
+    /// (i.e output-Sheet1.csv), the paths of the created files are returned
+    ///
+    /// Requires LibreOffice 7.2 or newer
+    pub fn convert_csv_sheets(
+        &self,
+        input: &Path,
+        output: &Path,
+        options: CsvFilterOptions,
+    ) -> Result<Vec<PathBuf>, OfficeError> {
+        let options = options.sheet(CsvSheet::All);
+
+        let output = output_path(output)?;
+        let output_url = path_url(&output)?;
+
+        let directory = output.parent().ok_or(OfficeError::InvalidPath)?;
+        let stem = output
+            .file_stem()
+            .and_then(|value| value.to_str())
+            .ok_or(OfficeError::InvalidPath)?;
+
+        let mut document = self.load_path(input, None)?;
+
+        let document_type = document.get_document_type()?;
+        if document_type != DocumentType::Spreadsheet {
+            return Err(OfficeError::UnsupportedExportFormat {
+                document_type,
+                format: "csv".to_string(),
+            });
+        }
+
+        // Snapshot existing files so only files written by this export are reported
+        let existing = csv_sheet_files(directory, stem)?;
+
+        document.save_as(&output_url, "csv", Some(&options.to_string()))?;
+
+        let mut files: Vec<PathBuf> = csv_sheet_files(directory, stem)?
+            .into_iter()
+            .filter(|(path, modified)| existing.get(path) != Some(modified))
+            .map(|(path, _)| path)
+            .collect();
+
+        if files.is_empty() {
+            return Err(OfficeError::ConversionFailed(output));
+        }
+
+        files.sort();
+
+        Ok(files)
+    }
+
+    /// Converts the document `input` bytes into the provided `output_format`
+    /// (i.e "pdf") returning the bytes of the converted document
+    ///
+    /// The input and output are written to a private temporary directory
+    /// which is removed once the conversion completes, fails or panics
+    pub fn convert_bytes(
+        &self,
+        input: &[u8],
+        input_hint: FormatHint,
+        output_format: &str,
+    ) -> Result<Vec<u8>, OfficeError> {
+        let temp_dir = tempfile::Builder::new()
+            .prefix("libreofficekit-")
+            .tempdir()?;
+
+        let input_path = match input_hint.extension() {
+            Some(extension) => temp_dir.path().join(format!("input.{extension}")),
+            None => temp_dir.path().join("input"),
+        };
+        let output_path = temp_dir.path().join("output");
+
+        std::fs::write(&input_path, input)?;
+
+        self.convert(
+            &input_path,
+            &output_path,
+            ConvertOptions::new().format(output_format),
+        )?;
+
+        let output = std::fs::read(&output_path)?;
+
+        Ok(output)
+    }
+
+    /// Loads the document at the provided local `path`
+    fn load_path(
+        &self,
+        path: &Path,
+        load_options: Option<&LoadOptions>,
+    ) -> Result<Document, OfficeError> {
+        let path = std::fs::canonicalize(path)?;
+        let url = path_url(&path)?;
+
+        match load_options {
+            Some(load_options) => self.document_load_with_options(&url, load_options),
+            None => self.document_load(&url),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::path::Path;
+
+    use super::{infer_format, FormatHint};
+
+    /// Tests the output format is inferred from the path extension
+    #[test]
+    fn test_infer_format() {
+        assert_eq!(
+            infer_format(Path::new("/tmp/out.PDF")).as_deref(),
+            Some("pdf")
+        );
+        assert_eq!(
+            infer_format(Path::new("out.tar.xlsx")).as_deref(),
+            Some("xlsx")
+        );
+        assert_eq!(infer_format(Path::new("/tmp/out")), None);
+    }
+
+    /// Tests format hints resolve to safe file extensions
+    #[test]
+    fn test_format_hint_extension() {
+        assert_eq!(
+            FormatHint::Extension(".DOCX".to_string())
+                .extension()
+                .as_deref(),
+            Some("docx")
+        );
+        assert_eq!(
+            FormatHint::MimeType("text/plain; charset=utf-8".to_string())
+                .extension()
+                .as_deref(),
+            Some("txt")
+        );
+        assert_eq!(
+            FormatHint::Extension("../../etc/passwd".to_string()).extension(),
+            None
+        );
+        assert_eq!(
+            FormatHint::MimeType("application/unknown".to_string()).extension(),
+            None
+        );
+        assert_eq!(FormatHint::None.extension(), None);
+    }
+}
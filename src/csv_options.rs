@@ -0,0 +1,293 @@
+use std::fmt;
+
+use crate::TextEncoding;
+
+/// Options for the CSV filter ("Text - txt - csv (StarCalc)")
+///
+/// The same options are used for importing and exporting, they can be
+/// provided to [LoadOptions::filter_options] when loading and to
+/// [Document::save_as] when saving using [ToString::to_string]:
+///
+/// ```
+/// use libreofficekit::{CsvFilterOptions, TextEncoding};
+///
+/// let options = CsvFilterOptions::new()
+///     .field_separator(';')
+///     .encoding(TextEncoding::Windows1252);
+///
+/// assert_eq!(
+///     options.to_string(),
+///     "59,34,1,1,,,false,true,true,false,false,0"
+/// );
+/// ```
+///
+/// [LoadOptions::filter_options]: crate::LoadOptions::filter_options
+/// [Document::save_as]: crate::Document::save_as
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CsvFilterOptions {
+    /// Characters separating fields
+    field_separators: Vec<char>,
+    /// Character surrounding text fields
+    text_delimiter: Option<char>,
+    /// Character encoding of the file
+    encoding: TextEncoding,
+    /// Line number (1-based) of the first line to import
+    first_line: u32,
+    /// Formats for specific columns (1-based column number)
+    column_formats: Vec<(u32, CsvColumnFormat)>,
+    /// Language identifier (LCID) used for parsing numbers and dates
+    language: Option<u16>,
+    /// Import: Treat quoted fields as text, Export: Quote all text cells
+    quote_all_text: bool,
+    /// Import: Detect special numbers such as dates
+    detect_special_numbers: bool,
+    /// Export: Save cell content as shown (With formatting)
+    save_as_shown: bool,
+    /// Export: Save cell formulas instead of values
+    export_formulas: bool,
+    /// Import: Trim spaces from fields
+    trim_spaces: bool,
+    /// Export: Sheets to export
+    sheet: CsvSheet,
+}
+
+impl Default for CsvFilterOptions {
+    fn default() -> Self {
+        Self {
+            field_separators: vec![','],
+            text_delimiter: Some('"'),
+            encoding: TextEncoding::Utf8,
+            first_line: 1,
+            column_formats: Vec::new(),
+            language: None,
+            quote_all_text: false,
+            detect_special_numbers: true,
+            save_as_shown: true,
+            export_formulas: false,
+            trim_spaces: false,
+            sheet: CsvSheet::First,
+        }
+    }
+}
+
+impl CsvFilterOptions {
+    /// Creates the default CSV options (Comma separated, double quoted, UTF-8)
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets a single character used to separate fields
+    pub fn field_separator(mut self, separator: char) -> Self {
+        self.field_separators = vec![separator];
+        self
+    }
+
+    /// Sets multiple characters that are all treated as field separators (Import only)
+    pub fn field_separators<I: IntoIterator<Item = char>>(mut self, separators: I) -> Self {
+        self.field_separators = separators.into_iter().collect();
+        self
+    }
+
+    /// Sets the character surrounding text fields, [None] to disable
+    pub fn text_delimiter(mut self, delimiter: Option<char>) -> Self {
+        self.text_delimiter = delimiter;
+        self
+    }
+
+    /// Sets the character encoding of the file
+    pub fn encoding(mut self, encoding: TextEncoding) -> Self {
+        self.encoding = encoding;
+        self
+    }
+
+    /// Sets the line number (1-based) of the first line to import
+    pub fn first_line(mut self, first_line: u32) -> Self {
+        self.first_line = first_line.max(1);
+        self
+    }
+
+    /// Sets the format used for a specific column (1-based) when importing
+    pub fn column_format(mut self, column: u32, format: CsvColumnFormat) -> Self {
+        self.column_formats.retain(|(value, _)| *value != column);
+        self.column_formats.push((column, format));
+        self
+    }
+
+    /// Sets the language identifier (Windows LCID i.e 1033 for en-US) used when
+    /// parsing numbers and dates
+    pub fn language(mut self, language: u16) -> Self {
+        self.language = Some(language);
+        self
+    }
+
+    /// When importing quoted fields are treated as text, when exporting
+    /// all text cells are quoted
+    pub fn quote_all_text(mut self, quote_all_text: bool) -> Self {
+        self.quote_all_text = quote_all_text;
+        self
+    }
+
+    /// Sets whether special numbers (Dates, scientific notation) are detected
+    /// when importing
+    pub fn detect_special_numbers(mut self, detect_special_numbers: bool) -> Self {
+        self.detect_special_numbers = detect_special_numbers;
+        self
+    }
+
+    /// Sets whether cell content is exported as shown (With number formatting)
+    pub fn save_as_shown(mut self, save_as_shown: bool) -> Self {
+        self.save_as_shown = save_as_shown;
+        self
+    }
+
+    /// Sets whether cell formulas are exported instead of their values
+    pub fn export_formulas(mut self, export_formulas: bool) -> Self {
+        self.export_formulas = export_formulas;
+        self
+    }
+
+    /// Sets whether leading and trailing spaces are trimmed when importing
+    pub fn trim_spaces(mut self, trim_spaces: bool) -> Self {
+        self.trim_spaces = trim_spaces;
+        self
+    }
+
+    /// Sets which sheets are exported, see [CsvSheet]
+    pub fn sheet(mut self, sheet: CsvSheet) -> Self {
+        self.sheet = sheet;
+        self
+    }
+}
+
+impl fmt::Display for CsvFilterOptions {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let field_separators = self
+            .field_separators
+            .iter()
+            .map(|value| (*value as u32).to_string())
+            .collect::<Vec<_>>()
+            .join("/");
+
+        let text_delimiter = self
+            .text_delimiter
+            .map(|value| (value as u32).to_string())
+            .unwrap_or_default();
+
+        let column_formats = self
+            .column_formats
+            .iter()
+            .map(|(column, format)| format!("{}/{}", column, *format as u8))
+            .collect::<Vec<_>>()
+            .join("/");
+
+        let language = self
+            .language
+            .map(|value| value.to_string())
+            .unwrap_or_default();
+
+        let sheet = match self.sheet {
+            CsvSheet::First => 0,
+            CsvSheet::Index(index) => index as i64,
+            CsvSheet::All => -1,
+        };
+
+        write!(
+            f,
+            "{},{},{},{},{},{},{},{},{},{},{},{}",
+            field_separators,
+            text_delimiter,
+            self.encoding.code(),
+            self.first_line,
+            column_formats,
+            language,
+            self.quote_all_text,
+            self.detect_special_numbers,
+            self.save_as_shown,
+            self.export_formulas,
+            self.trim_spaces,
+            sheet
+        )
+    }
+}
+
+/// Sheets to export when saving a spreadsheet as CSV
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum CsvSheet {
+    /// Export only the first sheet
+    #[default]
+    First,
+    /// Export only the sheet with the provided index (1-based)
+    Index(u32),
+    /// Export every sheet to a separate file, each file is named after
+    /// the output file with the sheet name appended (i.e output-Sheet1.csv)
+    ///
+    /// Requires LibreOffice 7.2 or newer
+    All,
+}
+
+/// Format used for a column when importing CSV
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+pub enum CsvColumnFormat {
+    /// Detect the format automatically
+    Standard = 1,
+    /// Treat the column as text
+    Text = 2,
+    /// Dates in month/day/year order
+    DateMdy = 3,
+    /// Dates in day/month/year order
+    DateDmy = 4,
+    /// Dates in year/month/day order
+    DateYmd = 5,
+    /// Skip the column
+    Ignore = 9,
+    /// Numbers in US English format regardless of language
+    UsEnglish = 10,
+}
+
+#[cfg(test)]
+mod test {
+    use super::{CsvColumnFormat, CsvFilterOptions, CsvSheet};
+    use crate::TextEncoding;
+
+    /// Tests the default options
+    #[test]
+    fn test_default() {
+        assert_eq!(
+            CsvFilterOptions::new().to_string(),
+            "44,34,76,1,,,false,true,true,false,false,0"
+        );
+    }
+
+    /// Tests all options serialize into their token positions
+    #[test]
+    fn test_all_options() {
+        let options = CsvFilterOptions::new()
+            .field_separators(['\t', ';'])
+            .text_delimiter(None)
+            .encoding(TextEncoding::Iso8859_15)
+            .first_line(2)
+            .column_format(1, CsvColumnFormat::Text)
+            .column_format(3, CsvColumnFormat::DateDmy)
+            .column_format(1, CsvColumnFormat::Ignore)
+            .language(1033)
+            .quote_all_text(true)
+            .detect_special_numbers(false)
+            .save_as_shown(false)
+            .export_formulas(true)
+            .trim_spaces(true)
+            .sheet(CsvSheet::Index(3));
+
+        assert_eq!(
+            options.to_string(),
+            "9/59,,22,2,3/4/1/9,1033,true,false,false,true,true,3"
+        );
+    }
+
+    /// Tests exporting all sheets
+    #[test]
+    fn test_all_sheets() {
+        let options = CsvFilterOptions::new().sheet(CsvSheet::All);
+        assert!(options.to_string().ends_with(",-1"));
+    }
+}
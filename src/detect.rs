@@ -0,0 +1,505 @@
+use std::{
+    fs::File,
+    io::{Read, Seek, SeekFrom},
+    path::Path,
+};
+
+use crate::{
+    filters::{FilterDirection, KNOWN_FILTERS},
+    DocUrl, DocumentType, FilterTypes, Office, OfficeError,
+};
+
+/// Format of a document detected by [Office::detect_format]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DetectedFormat {
+    /// Type of document
+    pub document_type: DocumentType,
+    /// Name of the import filter matching the format
+    pub filter_name: &'static str,
+    /// Name of the type detection type of the format, see [KnownFilter::type_name]
+    ///
+    /// [KnownFilter::type_name]: crate::KnownFilter::type_name
+    pub type_name: &'static str,
+    /// Mime type of the format
+    pub mime_type: &'static str,
+    /// Whether the document is encrypted (Password protected OOXML)
+    pub encrypted: bool,
+}
+
+impl DetectedFormat {
+    /// Creates the detected format from the preferred import filter for `mime`
+    fn from_mime(mime: &str) -> Option<Self> {
+        KNOWN_FILTERS
+            .iter()
+            .find(|filter| {
+                filter.direction.contains(FilterDirection::IMPORT) && filter.media_type == mime
+            })
+            .map(|filter| DetectedFormat {
+                document_type: filter.document_type,
+                filter_name: filter.name,
+                type_name: filter.type_name,
+                mime_type: filter.media_type,
+                encrypted: false,
+            })
+    }
+
+    /// Creates the detected format from the preferred import filter for the
+    /// file `extension`, limited to the provided `allowed` extensions
+    fn from_extension(extension: Option<&str>, allowed: &[&str]) -> Option<Self> {
+        let extension = extension?.to_ascii_lowercase();
+
+        if !allowed.contains(&extension.as_str()) {
+            return None;
+        }
+
+        KNOWN_FILTERS
+            .iter()
+            .find(|filter| {
+                filter.direction.contains(FilterDirection::IMPORT)
+                    && filter.extensions.contains(&extension.as_str())
+            })
+            .map(|filter| DetectedFormat {
+                document_type: filter.document_type,
+                filter_name: filter.name,
+                type_name: filter.type_name,
+                mime_type: filter.media_type,
+                encrypted: false,
+            })
+    }
+
+    /// Whether the format is available in an install reporting `filter_types`
+    /// (See [Office::get_filter_types])
+    fn is_available(&self, filter_types: &FilterTypes) -> bool {
+        filter_types.values.contains_key(self.type_name)
+    }
+}
+
+/// Number of bytes read from the start of the file for detection
+const HEADER_LENGTH: u64 = 4096;
+
+/// Maximum size of a zip central directory that will be read
+const MAX_CENTRAL_DIRECTORY_LENGTH: u64 = 4 * 1024 * 1024;
+
+/// Extensions of formats that have no magic bytes and are accepted
+/// based on their extension when the content looks like text
+const TEXT_EXTENSIONS: &[&str] = &["txt", "csv"];
+
+/// Extensions of OOXML formats that may be stored in an encrypted container
+const OOXML_EXTENSIONS: &[&str] = &["docx", "docm", "xlsx", "xlsm", "pptx", "pptm"];
+
+/// Detects the format of the local file at `path` from its content without
+/// loading the document, the file extension is only used for formats which
+/// cannot be identified from their content alone
+///
+/// Returns [None] when the format could not be detected
+pub fn detect_file(path: &Path) -> std::io::Result<Option<DetectedFormat>> {
+    let mut file = File::open(path)?;
+
+    let mut header = Vec::new();
+    (&mut file).take(HEADER_LENGTH).read_to_end(&mut header)?;
+
+    let extension = path.extension().and_then(|value| value.to_str());
+
+    if header.starts_with(b"PK\x03\x04") {
+        return detect_zip(&mut file, &header);
+    }
+
+    if header.starts_with(&[0xD0, 0xCF, 0x11, 0xE0, 0xA1, 0xB1, 0x1A, 0xE1]) {
+        return detect_cfb(&mut file, &header, extension);
+    }
+
+    Ok(detect_magic(&header, extension))
+}
+
+/// Detects formats identified by a magic prefix or that are plain text
+fn detect_magic(header: &[u8], extension: Option<&str>) -> Option<DetectedFormat> {
+    if header.starts_with(b"%PDF-") {
+        return DetectedFormat::from_mime("application/pdf");
+    }
+
+    if header.starts_with(b"{\\rtf") {
+        return DetectedFormat::from_mime("application/rtf");
+    }
+
+    let text = String::from_utf8_lossy(header);
+    let text = text.trim_start_matches('\u{FEFF}').trim_start();
+
+    // Flat XML OpenDocument formats declare their mime type on the root element
+    if text.starts_with("<?xml") {
+        if let Some(mime) = flat_xml_mime(text) {
+            return DetectedFormat::from_mime(mime);
+        }
+    }
+
+    // Compared as bytes as the prefix may end within a multibyte character
+    let lowercase = text.as_bytes()[..text.len().min(512)].to_ascii_lowercase();
+    if lowercase.starts_with(b"<!doctype html") || lowercase.starts_with(b"<html") {
+        return DetectedFormat::from_mime("text/html");
+    }
+
+    // Binary content is never accepted as text
+    if header.contains(&0) {
+        return None;
+    }
+
+    DetectedFormat::from_extension(extension, TEXT_EXTENSIONS)
+}
+
+/// Finds the `office:mimetype` attribute of a flat XML OpenDocument
+fn flat_xml_mime(text: &str) -> Option<&'static str> {
+    const ATTRIBUTE: &str = "office:mimetype=\"";
+
+    let start = text.find(ATTRIBUTE)? + ATTRIBUTE.len();
+    let end = start + text[start..].find('"')?;
+    let mime = &text[start..end];
+
+    // Flat formats use the mime type of their packaged equivalent
+    let flat_mime = match mime {
+        "application/vnd.oasis.opendocument.text" => {
+            "application/vnd.oasis.opendocument.text-flat-xml"
+        }
+        "application/vnd.oasis.opendocument.spreadsheet" => {
+            "application/vnd.oasis.opendocument.spreadsheet-flat-xml"
+        }
+        "application/vnd.oasis.opendocument.presentation" => {
+            "application/vnd.oasis.opendocument.presentation-flat-xml"
+        }
+        "application/vnd.oasis.opendocument.graphics" => {
+            "application/vnd.oasis.opendocument.graphics-flat-xml"
+        }
+        _ => return None,
+    };
+
+    Some(flat_mime)
+}
+
+/// Detects OpenDocument and OOXML formats stored in a zip package
+fn detect_zip(file: &mut File, header: &[u8]) -> std::io::Result<Option<DetectedFormat>> {
+    // OpenDocument packages store an uncompressed "mimetype" entry first
+    if let Some(mime) = odf_mimetype(header) {
+        return Ok(DetectedFormat::from_mime(&mime));
+    }
+
+    let names = zip_entry_names(file)?;
+    let has = |name: &str| names.iter().any(|value| value == name);
+
+    let mime = if has("word/document.xml") {
+        if has("word/vbaProject.bin") {
+            "application/vnd.ms-word.document.macroEnabled.12"
+        } else {
+            "application/vnd.openxmlformats-officedocument.wordprocessingml.document"
+        }
+    } else if has("xl/workbook.xml") {
+        if has("xl/vbaProject.bin") {
+            "application/vnd.ms-excel.sheet.macroEnabled.12"
+        } else {
+            "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet"
+        }
+    } else if has("ppt/presentation.xml") {
+        if has("ppt/vbaProject.bin") {
+            "application/vnd.ms-powerpoint.presentation.macroEnabled.12"
+        } else {
+            "application/vnd.openxmlformats-officedocument.presentationml.presentation"
+        }
+    } else {
+        return Ok(None);
+    };
+
+    Ok(DetectedFormat::from_mime(mime))
+}
+
+/// Reads the mime type from the first "mimetype" entry of an OpenDocument package
+fn odf_mimetype(header: &[u8]) -> Option<String> {
+    let compression = read_u16(header, 8)?;
+    let size = read_u32(header, 18)? as usize;
+    let name_length = read_u16(header, 26)? as usize;
+    let extra_length = read_u16(header, 28)? as usize;
+
+    let name = header.get(30..30 + name_length)?;
+    if name != b"mimetype" || compression != 0 {
+        return None;
+    }
+
+    let start = 30 + name_length + extra_length;
+    let value = header.get(start..start + size)?;
+
+    String::from_utf8(value.to_vec()).ok()
+}
+
+/// Reads the names of all the entries from the zip central directory
+fn zip_entry_names(file: &mut File) -> std::io::Result<Vec<String>> {
+    /// End of central directory record signature
+    const EOCD_SIGNATURE: &[u8] = b"PK\x05\x06";
+    /// Central directory file header signature
+    const ENTRY_SIGNATURE: &[u8] = b"PK\x01\x02";
+
+    let length = file.metadata()?.len();
+
+    // End of central directory is within the last 22 bytes + max comment length
+    let tail_length = length.min(22 + u16::MAX as u64);
+    file.seek(SeekFrom::Start(length - tail_length))?;
+
+    let mut tail = Vec::new();
+    file.read_to_end(&mut tail)?;
+
+    let Some(eocd) = tail
+        .windows(EOCD_SIGNATURE.len())
+        .rposition(|value| value == EOCD_SIGNATURE)
+    else {
+        return Ok(Vec::new());
+    };
+
+    let (Some(directory_length), Some(directory_offset)) =
+        (read_u32(&tail, eocd + 12), read_u32(&tail, eocd + 16))
+    else {
+        return Ok(Vec::new());
+    };
+
+    let directory_length = directory_length as u64;
+    let directory_offset = directory_offset as u64;
+
+    // Zip64 and oversized directories are not supported
+    if directory_offset == u32::MAX as u64
+        || directory_length > MAX_CENTRAL_DIRECTORY_LENGTH
+        || directory_offset + directory_length > length
+    {
+        return Ok(Vec::new());
+    }
+
+    file.seek(SeekFrom::Start(directory_offset))?;
+
+    let mut directory = vec![0; directory_length as usize];
+    file.read_exact(&mut directory)?;
+
+    let mut names = Vec::new();
+    let mut offset = 0;
+
+    while directory.get(offset..offset + 4) == Some(ENTRY_SIGNATURE) {
+        let (Some(name_length), Some(extra_length), Some(comment_length)) = (
+            read_u16(&directory, offset + 28),
+            read_u16(&directory, offset + 30),
+            read_u16(&directory, offset + 32),
+        ) else {
+            break;
+        };
+
+        let name_start = offset + 46;
+        let Some(name) = directory.get(name_start..name_start + name_length as usize) else {
+            break;
+        };
+
+        names.push(String::from_utf8_lossy(name).into_owned());
+
+        offset =
+            name_start + name_length as usize + extra_length as usize + comment_length as usize;
+    }
+
+    Ok(names)
+}
+
+/// Detects legacy Microsoft Office formats and encrypted OOXML documents
+/// stored in a compound file binary (OLE) container
+///
+/// Only the stream names within the first directory sector are checked
+fn detect_cfb(
+    file: &mut File,
+    header: &[u8],
+    extension: Option<&str>,
+) -> std::io::Result<Option<DetectedFormat>> {
+    let (Some(sector_shift), Some(directory_sector)) =
+        (read_u16(header, 0x1E), read_u32(header, 0x30))
+    else {
+        return Ok(None);
+    };
+
+    if !(7..=16).contains(&sector_shift) {
+        return Ok(None);
+    }
+
+    let sector_size = 1u64 << sector_shift;
+    file.seek(SeekFrom::Start((directory_sector as u64 + 1) * sector_size))?;
+
+    let mut directory = Vec::new();
+    file.take(sector_size).read_to_end(&mut directory)?;
+
+    // Directory entries are 128 bytes with a UTF-16 name and its byte length at 64
+    let names: Vec<String> = directory
+        .chunks_exact(128)
+        .filter_map(|entry| {
+            let name_length = read_u16(entry, 64)? as usize;
+            let name = entry.get(..name_length.saturating_sub(2))?;
+            let name: Vec<u16> = name
+                .chunks_exact(2)
+                .map(|value| u16::from_le_bytes([value[0], value[1]]))
+                .collect();
+            String::from_utf16(&name).ok()
+        })
+        .collect();
+
+    let has = |name: &str| names.iter().any(|value| value == name);
+
+    let mime = if has("EncryptedPackage") {
+        // Contents are encrypted, the extension is the only hint to the format
+        let format = DetectedFormat::from_extension(extension, OOXML_EXTENSIONS);
+        return Ok(format.map(|format| DetectedFormat {
+            encrypted: true,
+            ..format
+        }));
+    } else if has("WordDocument") {
+        "application/msword"
+    } else if has("Workbook") || has("Book") {
+        "application/vnd.ms-excel"
+    } else if has("PowerPoint Document") {
+        "application/vnd.ms-powerpoint"
+    } else {
+        return Ok(None);
+    };
+
+    Ok(DetectedFormat::from_mime(mime))
+}
+
+fn read_u16(bytes: &[u8], offset: usize) -> Option<u16> {
+    let value = bytes.get(offset..offset + 2)?;
+    Some(u16::from_le_bytes([value[0], value[1]]))
+}
+
+fn read_u32(bytes: &[u8], offset: usize) -> Option<u32> {
+    let value = bytes.get(offset..offset + 4)?;
+    Some(u32::from_le_bytes([value[0], value[1], value[2], value[3]]))
+}
+
+impl Office {
+    /// Detects the format of the local document at `url` without loading
+    /// the document, see [detect_file]
+    ///
+    /// The type of the detected format is checked against the types available
+    /// in the install when [Office::get_filter_types] is supported
+    pub fn detect_format(&self, url: &DocUrl) -> Result<DetectedFormat, OfficeError> {
+        let path = url.to_file_path().ok_or(OfficeError::InvalidPath)?;
+        let format = detect_file(&path)?.ok_or(OfficeError::UnknownFormat)?;
+
+        match self.get_filter_types() {
+            Ok(filter_types) => {
+                if !format.is_available(&filter_types) {
+                    return Err(OfficeError::FilterUnavailable(format.filter_name));
+                }
+            }
+            // Older installs cannot report their filters
+            Err(OfficeError::MissingFunction(_)) => {}
+            Err(err) => return Err(err),
+        }
+
+        Ok(format)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::{collections::HashMap, path::Path};
+
+    use super::{detect_file, detect_magic};
+    use crate::{DocumentType, FilterType, FilterTypes};
+
+    fn sample(name: &str) -> std::path::PathBuf {
+        Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("tests/samples")
+            .join(name)
+    }
+
+    /// Tests detecting formats from magic bytes
+    #[test]
+    fn test_detect_magic() {
+        let format = detect_magic(b"%PDF-1.7\n", None).unwrap();
+        assert_eq!(format.filter_name, "draw_pdf_import");
+        assert_eq!(format.document_type, DocumentType::Drawing);
+
+        let format = detect_magic(b"{\\rtf1\\ansi", Some("doc")).unwrap();
+        assert_eq!(format.filter_name, "Rich Text Format");
+
+        let format = detect_magic(
+            b"<?xml version=\"1.0\"?><office:document office:mimetype=\"application/vnd.oasis.opendocument.spreadsheet\">",
+            None,
+        )
+        .unwrap();
+        assert_eq!(format.filter_name, "OpenDocument Spreadsheet Flat XML");
+
+        let format = detect_magic(b"<!DOCTYPE html><html>", None).unwrap();
+        assert_eq!(format.filter_name, "HTML (StarWriter)");
+
+        // Multibyte character spanning the 512 byte prefix
+        let mut text = "a".repeat(511);
+        text.push_str("é,b\n1,2\n");
+        let format = detect_magic(text.as_bytes(), Some("csv")).unwrap();
+        assert_eq!(format.document_type, DocumentType::Spreadsheet);
+
+        let format = detect_magic(b"a,b,c\n1,2,3\n", Some("CSV")).unwrap();
+        assert_eq!(format.document_type, DocumentType::Spreadsheet);
+
+        // Binary content is not accepted based on the extension
+        assert!(detect_magic(b"a\0b\0c", Some("txt")).is_none());
+        assert!(detect_magic(b"not a document", Some("docx")).is_none());
+    }
+
+    /// Tests detecting the sample documents
+    #[test]
+    fn test_detect_samples() {
+        let format = detect_file(&sample("sample-docx.docx")).unwrap().unwrap();
+        assert_eq!(format.filter_name, "MS Word 2007 XML");
+        assert_eq!(format.document_type, DocumentType::Text);
+        assert!(!format.encrypted);
+
+        let format = detect_file(&sample("sample-xlsx.xlsx")).unwrap().unwrap();
+        assert_eq!(format.filter_name, "Calc MS Excel 2007 XML");
+        assert_eq!(format.document_type, DocumentType::Spreadsheet);
+
+        let format = detect_file(&sample("sample-docx-encrypted.docx"))
+            .unwrap()
+            .unwrap();
+        assert_eq!(format.filter_name, "MS Word 2007 XML");
+        assert!(format.encrypted);
+
+        let format = detect_file(&sample("sample-text.txt")).unwrap().unwrap();
+        assert_eq!(format.filter_name, "Text");
+    }
+
+    /// Tests detected formats are checked against the type names reported
+    /// by getFilterTypes
+    #[test]
+    fn test_detect_available() {
+        let filter_types = FilterTypes {
+            values: [
+                (
+                    "writer_MS_Word_2007",
+                    "application/vnd.openxmlformats-officedocument.wordprocessingml.document",
+                ),
+                (
+                    "calc_MS_Excel_2007_XML",
+                    "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet",
+                ),
+                ("pdf_Portable_Document_Format", "application/pdf"),
+            ]
+            .into_iter()
+            .map(|(name, media_type)| {
+                (
+                    name.to_string(),
+                    FilterType {
+                        media_type: media_type.to_string(),
+                    },
+                )
+            })
+            .collect::<HashMap<_, _>>(),
+        };
+
+        let format = detect_file(&sample("sample-docx.docx")).unwrap().unwrap();
+        assert!(format.is_available(&filter_types));
+
+        let format = detect_file(&sample("sample-xlsx.xlsx")).unwrap().unwrap();
+        assert!(format.is_available(&filter_types));
+
+        let format = detect_magic(b"%PDF-1.7\n", None).unwrap();
+        assert!(format.is_available(&filter_types));
+
+        let format = detect_file(&sample("sample-text.txt")).unwrap().unwrap();
+        assert!(!format.is_available(&filter_types));
+    }
+}
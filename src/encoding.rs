@@ -0,0 +1,171 @@
+use std::{io::Read, path::Path};
+
+/// Character encodings understood by LibreOffice filters (`rtl_TextEncoding`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TextEncoding {
+    /// UTF-8
+    Utf8,
+    /// UTF-16 (Little endian with byte-order mark)
+    Utf16,
+    /// US-ASCII
+    Ascii,
+    /// ISO-8859-1 (Western European)
+    Iso8859_1,
+    /// ISO-8859-2 (Central European)
+    Iso8859_2,
+    /// ISO-8859-5 (Cyrillic)
+    Iso8859_5,
+    /// ISO-8859-15 (Western European with Euro sign)
+    Iso8859_15,
+    /// Windows-1250 (Central European)
+    Windows1250,
+    /// Windows-1251 (Cyrillic)
+    Windows1251,
+    /// Windows-1252 (Western European)
+    Windows1252,
+    /// IBM-437 (DOS United States)
+    Ibm437,
+    /// IBM-850 (DOS Western European)
+    Ibm850,
+    /// Mac Roman
+    AppleRoman,
+    /// Shift-JIS (Japanese)
+    ShiftJis,
+    /// EUC-JP (Japanese)
+    EucJp,
+    /// GBK (Simplified Chinese)
+    Gbk,
+    /// Big5 (Traditional Chinese)
+    Big5,
+    /// EUC-KR (Korean)
+    EucKr,
+    /// KOI8-R (Russian)
+    Koi8R,
+    /// Any other `rtl_TextEncoding` value
+    Other(u16),
+}
+
+impl TextEncoding {
+    /// Numeric `rtl_TextEncoding` code for the encoding
+    pub fn code(&self) -> u16 {
+        match self {
+            TextEncoding::Utf8 => 76,
+            TextEncoding::Utf16 => 0xFFFF,
+            TextEncoding::Ascii => 11,
+            TextEncoding::Iso8859_1 => 12,
+            TextEncoding::Iso8859_2 => 13,
+            TextEncoding::Iso8859_5 => 16,
+            TextEncoding::Iso8859_15 => 22,
+            TextEncoding::Windows1250 => 33,
+            TextEncoding::Windows1251 => 34,
+            TextEncoding::Windows1252 => 1,
+            TextEncoding::Ibm437 => 3,
+            TextEncoding::Ibm850 => 4,
+            TextEncoding::AppleRoman => 2,
+            TextEncoding::ShiftJis => 64,
+            TextEncoding::EucJp => 69,
+            TextEncoding::Gbk => 67,
+            TextEncoding::Big5 => 68,
+            TextEncoding::EucKr => 79,
+            TextEncoding::Koi8R => 74,
+            TextEncoding::Other(code) => *code,
+        }
+    }
+
+    /// Name of the encoding as understood by the text filters, [None] for
+    /// [TextEncoding::Other] encodings which have no known name
+    pub fn name(&self) -> Option<&'static str> {
+        Some(match self {
+            TextEncoding::Utf8 => "UTF8",
+            TextEncoding::Utf16 => "UNICODE",
+            TextEncoding::Ascii => "ASCII_US",
+            TextEncoding::Iso8859_1 => "ISO_8859_1",
+            TextEncoding::Iso8859_2 => "ISO_8859_2",
+            TextEncoding::Iso8859_5 => "ISO_8859_5",
+            TextEncoding::Iso8859_15 => "ISO_8859_15",
+            TextEncoding::Windows1250 => "MS_1250",
+            TextEncoding::Windows1251 => "MS_1251",
+            TextEncoding::Windows1252 => "MS_1252",
+            TextEncoding::Ibm437 => "IBM_437",
+            TextEncoding::Ibm850 => "IBM_850",
+            TextEncoding::AppleRoman => "APPLE_ROMAN",
+            TextEncoding::ShiftJis => "SHIFT_JIS",
+            TextEncoding::EucJp => "EUC_JP",
+            TextEncoding::Gbk => "GBK",
+            TextEncoding::Big5 => "BIG5",
+            TextEncoding::EucKr => "EUC_KR",
+            TextEncoding::Koi8R => "KOI8_R",
+            TextEncoding::Other(_) => return None,
+        })
+    }
+
+    /// Detects the encoding of the provided text bytes
+    ///
+    /// Uses the byte-order mark when present, otherwise text that is valid
+    /// UTF-8 is detected as [TextEncoding::Utf8] and anything else falls back
+    /// to [TextEncoding::Windows1252] the most common legacy encoding
+    pub fn detect(bytes: &[u8]) -> TextEncoding {
+        if bytes.starts_with(&[0xEF, 0xBB, 0xBF]) {
+            return TextEncoding::Utf8;
+        }
+
+        if bytes.starts_with(&[0xFF, 0xFE]) || bytes.starts_with(&[0xFE, 0xFF]) {
+            return TextEncoding::Utf16;
+        }
+
+        match std::str::from_utf8(bytes) {
+            Ok(_) => TextEncoding::Utf8,
+            // Sequence was only cut off at the end of the sample
+            Err(err) if err.error_len().is_none() => TextEncoding::Utf8,
+            Err(_) => TextEncoding::Windows1252,
+        }
+    }
+
+    /// Detects the encoding of the text file at `path` from a sample of
+    /// its leading bytes, see [TextEncoding::detect]
+    pub fn detect_file(path: &Path) -> std::io::Result<TextEncoding> {
+        /// Number of bytes sampled from the file
+        const SAMPLE_LENGTH: u64 = 64 * 1024;
+
+        let mut sample = Vec::new();
+        std::fs::File::open(path)?
+            .take(SAMPLE_LENGTH)
+            .read_to_end(&mut sample)?;
+
+        Ok(Self::detect(&sample))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::TextEncoding;
+
+    /// Tests detecting encodings from byte-order marks
+    #[test]
+    fn test_detect_bom() {
+        assert_eq!(
+            TextEncoding::detect(&[0xEF, 0xBB, 0xBF, b'a']),
+            TextEncoding::Utf8
+        );
+        assert_eq!(
+            TextEncoding::detect(&[0xFF, 0xFE, b'a', 0]),
+            TextEncoding::Utf16
+        );
+    }
+
+    /// Tests detecting encodings without a byte-order mark
+    #[test]
+    fn test_detect_heuristic() {
+        assert_eq!(TextEncoding::detect("café".as_bytes()), TextEncoding::Utf8);
+        // Multi-byte sequence cut off by the end of the sample
+        assert_eq!(
+            TextEncoding::detect(&"café".as_bytes()[..4]),
+            TextEncoding::Utf8
+        );
+        // "café" encoded as Windows-1252
+        assert_eq!(
+            TextEncoding::detect(&[b'c', b'a', b'f', 0xE9, b' ']),
+            TextEncoding::Windows1252
+        );
+    }
+}
@@ -1,7 +1,9 @@
-use std::{ffi::NulError, str::Utf8Error};
+use std::{ffi::NulError, path::PathBuf, str::Utf8Error};
 
 use thiserror::Error;
 
+use crate::{DocUrl, DocumentType, FullVersion};
+
 #[derive(Debug, Error)]
 pub enum OfficeError {
     /// The library files did not exist
@@ -16,10 +18,81 @@ pub enum OfficeError {
     #[error(transparent)]
     LoadLibrary(dlopen2::Error),
 
-    /// Error message produced by office
+    /// Error message produced by this crate, errors reported by office
+    /// are classified into the variants below
     #[error("{0}")]
     OfficeError(String),
 
+    /// Office could not detect or does not support the document format
+    #[error("unsupported format: {message}")]
+    UnsupportedFormat {
+        /// Document the error occurred for
+        url: Option<DocUrl>,
+        /// Error message reported by office
+        message: String,
+        /// Lines written to stderr during the failed call, empty unless
+        /// capturing is enabled using [Office::set_stderr_capture]
+        ///
+        /// [Office::set_stderr_capture]: crate::Office::set_stderr_capture
+        stderr: Vec<String>,
+    },
+
+    /// Document file does not exist
+    #[error("file not found: {message}")]
+    FileNotFound {
+        /// Document the error occurred for
+        url: Option<DocUrl>,
+        /// Error message reported by office
+        message: String,
+        /// Lines written to stderr during the failed call, empty unless
+        /// capturing is enabled using [Office::set_stderr_capture]
+        ///
+        /// [Office::set_stderr_capture]: crate::Office::set_stderr_capture
+        stderr: Vec<String>,
+    },
+
+    /// Document is password protected and the password was missing or incorrect
+    #[error("password required: {message}")]
+    PasswordRequired {
+        /// Document the error occurred for
+        url: Option<DocUrl>,
+        /// Error message reported by office
+        message: String,
+        /// Lines written to stderr during the failed call, empty unless
+        /// capturing is enabled using [Office::set_stderr_capture]
+        ///
+        /// [Office::set_stderr_capture]: crate::Office::set_stderr_capture
+        stderr: Vec<String>,
+    },
+
+    /// Office failed to read or write the document
+    #[error("input/output error: {message}")]
+    IoError {
+        /// Document the error occurred for
+        url: Option<DocUrl>,
+        /// Error message reported by office
+        message: String,
+        /// Lines written to stderr during the failed call, empty unless
+        /// capturing is enabled using [Office::set_stderr_capture]
+        ///
+        /// [Office::set_stderr_capture]: crate::Office::set_stderr_capture
+        stderr: Vec<String>,
+    },
+
+    /// Error message reported by office that could not be classified
+    #[error("{message}")]
+    GeneralError {
+        /// Document the error occurred for
+        url: Option<DocUrl>,
+        /// Error message reported by office
+        message: String,
+        /// Lines written to stderr during the failed call, empty unless
+        /// capturing is enabled using [Office::set_stderr_capture]
+        ///
+        /// [Office::set_stderr_capture]: crate::Office::set_stderr_capture
+        stderr: Vec<String>,
+    },
+
     /// Function is not available in the current office install
     #[error("missing '{0}' function")]
     MissingFunction(&'static str),
@@ -40,14 +113,32 @@ pub enum OfficeError {
     #[error("string cannot contain null byte")]
     InvalidString(#[from] NulError),
 
+    /// Value provided for an office option was invalid
+    #[error("invalid value '{value}' for option '{option}'")]
+    InvalidOptionValue {
+        /// Name of the option
+        option: &'static str,
+        /// The invalid value
+        value: String,
+    },
+
     /// Provided path was invalid
     #[error("invalid path provided")]
     InvalidPath,
 
+    /// Install version is older than the minimum supported version
+    #[error("unsupported office version {0}")]
+    UnsupportedVersion(FullVersion),
+
     /// Prevented from creating another office instance
     #[error("already another active instance")]
     InstanceLock,
 
+    /// Office was already initialized and dropped within this process,
+    /// LibreOffice cannot be initialized again
+    #[error("office cannot be re-initialized within the same process")]
+    ReinitUnsupported,
+
     /// Office instance was dropped before a callback was invoked
     #[error("callback invoked after instance was dropped")]
     InstanceDropped,
@@ -55,4 +146,230 @@ pub enum OfficeError {
     /// Unknown error happened while initializing LOK
     #[error("unknown initialization error")]
     UnknownInit,
+
+    /// File system error
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+
+    /// Output format could not be inferred from the output path
+    #[error("unable to determine output format for {0}")]
+    UnknownOutputFormat(PathBuf),
+
+    /// Document type cannot be exported in the requested format
+    #[error("cannot export {document_type:?} document as '{format}'")]
+    UnsupportedExportFormat {
+        document_type: DocumentType,
+        format: String,
+    },
+
+    /// Document failed to convert or produced an empty output file
+    #[error("failed to convert document to {0}")]
+    ConversionFailed(PathBuf),
+
+    /// Office did not return a document and reported no error
+    #[error("failed to load document: {reason}")]
+    LoadFailed {
+        /// Document that was being loaded
+        url: DocUrl,
+        /// Diagnostics about why the document may have failed to load
+        reason: String,
+        /// Lines written to stderr while loading, empty unless capturing
+        /// is enabled using [Office::set_stderr_capture]
+        ///
+        /// [Office::set_stderr_capture]: crate::Office::set_stderr_capture
+        stderr: Vec<String>,
+    },
+
+    /// Document could not be saved
+    #[error("failed to save document as '{format}': {reason}")]
+    SaveFailed {
+        /// Destination the document was being saved to
+        url: DocUrl,
+        /// Format the document was being saved as
+        format: String,
+        /// Error message reported by office and any errors emitted while
+        /// saving, empty when office did not report a reason
+        reason: String,
+        /// Lines written to stderr while saving, empty unless capturing
+        /// is enabled using [Office::set_stderr_capture]
+        ///
+        /// [Office::set_stderr_capture]: crate::Office::set_stderr_capture
+        stderr: Vec<String>,
+    },
+
+    /// Format of the document could not be detected
+    #[error("unable to detect document format")]
+    UnknownFormat,
+
+    /// Detected filter is not available in the LibreOffice install
+    #[error("filter '{0}' is not available")]
+    FilterUnavailable(&'static str),
+
+    /// [Office::stop_trace] was called without a trace started by [Office::start_trace]
+    ///
+    /// [Office::stop_trace]: crate::Office::stop_trace
+    /// [Office::start_trace]: crate::Office::start_trace
+    #[error("no trace has been started")]
+    TraceNotStarted,
+
+    /// Document uses fonts that are not available, returned by conversions
+    /// using [ConvertOptions::strict_fonts]
+    ///
+    /// [ConvertOptions::strict_fonts]: crate::ConvertOptions::strict_fonts
+    #[error("document uses missing fonts: {}", .0.join(", "))]
+    FontsMissing(Vec<String>),
+}
+
+impl OfficeError {
+    /// Attaches the `lines` written to stderr during the failed call to
+    /// [OfficeError::LoadFailed] and errors reported by office
+    pub(crate) fn with_stderr(mut self, lines: Vec<String>) -> OfficeError {
+        match &mut self {
+            OfficeError::LoadFailed { stderr, .. }
+            | OfficeError::UnsupportedFormat { stderr, .. }
+            | OfficeError::FileNotFound { stderr, .. }
+            | OfficeError::PasswordRequired { stderr, .. }
+            | OfficeError::IoError { stderr, .. }
+            | OfficeError::GeneralError { stderr, .. } => *stderr = lines,
+            _ => {}
+        }
+
+        self
+    }
+
+    /// Classifies an error `message` reported by office (`getError`) into
+    /// one of the error variants, `url` is the document the operation was
+    /// performed on if any
+    pub(crate) fn from_office_message(message: String, url: Option<&DocUrl>) -> OfficeError {
+        let lower = message.to_lowercase();
+        let contains = |patterns: &[&str]| patterns.iter().any(|pattern| lower.contains(pattern));
+        let url = url.cloned();
+
+        // Office reports missing files as failed type detection, check the file itself
+        let missing_file = url
+            .as_ref()
+            .and_then(DocUrl::to_file_path)
+            .is_some_and(|path| !path.exists());
+
+        if contains(&["password", "encrypted"]) {
+            OfficeError::PasswordRequired {
+                url,
+                message,
+                stderr: Vec::new(),
+            }
+        } else if missing_file {
+            OfficeError::FileNotFound {
+                url,
+                message,
+                stderr: Vec::new(),
+            }
+        } else if contains(&[
+            "type detection failed",
+            "unsupported url",
+            "unsupported format",
+            "unknown format",
+            "filter not found",
+        ]) {
+            // Checked before the generic "not found" messages below
+            OfficeError::UnsupportedFormat {
+                url,
+                message,
+                stderr: Vec::new(),
+            }
+        } else if contains(&["not found", "does not exist", "no such file", "nonexistent"]) {
+            OfficeError::FileNotFound {
+                url,
+                message,
+                stderr: Vec::new(),
+            }
+        } else if contains(&[
+            "input/output",
+            "i/o error",
+            "ioexception",
+            "access denied",
+            "permission denied",
+            "read error",
+            "write error",
+        ]) {
+            OfficeError::IoError {
+                url,
+                message,
+                stderr: Vec::new(),
+            }
+        } else {
+            OfficeError::GeneralError {
+                url,
+                message,
+                stderr: Vec::new(),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::OfficeError;
+    use crate::DocUrl;
+
+    /// Tests office error messages are classified into variants
+    #[test]
+    fn test_from_office_message() {
+        let classify = |message: &str| OfficeError::from_office_message(message.to_string(), None);
+
+        assert!(matches!(
+            classify("Unsupported URL <file:///tmp/a.xyz>: \"type detection failed\""),
+            OfficeError::UnsupportedFormat { .. }
+        ));
+        assert!(matches!(
+            classify("Filter not found"),
+            OfficeError::UnsupportedFormat { .. }
+        ));
+        assert!(matches!(
+            classify("Source file could not be loaded: file does not exist"),
+            OfficeError::FileNotFound { .. }
+        ));
+        assert!(matches!(
+            classify("Wrong password"),
+            OfficeError::PasswordRequired { .. }
+        ));
+        assert!(matches!(
+            classify("General Error.\nGeneral input/output error."),
+            OfficeError::IoError { .. }
+        ));
+        assert!(matches!(
+            classify("loadComponentFromURL returned an empty reference"),
+            OfficeError::GeneralError { .. }
+        ));
+    }
+
+    /// Tests captured stderr lines are attached to classified office errors
+    #[test]
+    fn test_with_stderr() {
+        let lines = vec!["warn:sfx.doc:1:1: failed".to_string()];
+
+        let error = OfficeError::from_office_message("Filter not found".to_string(), None)
+            .with_stderr(lines.clone());
+
+        match error {
+            OfficeError::UnsupportedFormat { stderr, .. } => assert_eq!(stderr, lines),
+            error => panic!("unexpected error {error:?}"),
+        }
+    }
+
+    /// Tests missing local files are classified as not found regardless
+    /// of the message
+    #[test]
+    fn test_from_office_message_missing_file() {
+        let url = DocUrl::from_absolute_path("/tmp/libreofficekit-missing-file.docx").unwrap();
+        let error = OfficeError::from_office_message(
+            "Unsupported URL <file:///tmp/libreofficekit-missing-file.docx>: \"type detection failed\""
+                .to_string(),
+            Some(&url),
+        );
+
+        match error {
+            OfficeError::FileNotFound { url: error_url, .. } => assert_eq!(error_url, Some(url)),
+            error => panic!("unexpected error {error:?}"),
+        }
+    }
 }
@@ -0,0 +1,389 @@
+use bitflags::bitflags;
+
+use crate::{DocumentType, FilterTypes};
+
+bitflags! {
+    /// Directions a filter can be used in
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct FilterDirection: u8 {
+        /// Filter can load documents
+        const IMPORT = 1 << 0;
+        /// Filter can save documents
+        const EXPORT = 1 << 1;
+    }
+}
+
+/// Details about a well-known LibreOffice filter
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KnownFilter {
+    /// Name of the filter (i.e "writer_pdf_Export")
+    pub name: &'static str,
+    /// Name of the type detection type the filter handles, these are the
+    /// keys reported by [Office::get_filter_types] (i.e "pdf_Portable_Document_Format")
+    ///
+    /// [Office::get_filter_types]: crate::Office::get_filter_types
+    pub type_name: &'static str,
+    /// Type of document the filter applies to
+    pub document_type: DocumentType,
+    /// Directions the filter can be used in
+    pub direction: FilterDirection,
+    /// Mime type of the filter format
+    pub media_type: &'static str,
+    /// File extensions for the format, the first is the preferred extension
+    pub extensions: &'static [&'static str],
+}
+
+const IMPORT: FilterDirection = FilterDirection::IMPORT;
+const EXPORT: FilterDirection = FilterDirection::EXPORT;
+const BOTH: FilterDirection = FilterDirection::IMPORT.union(FilterDirection::EXPORT);
+
+/// Built-in table of well-known filters
+///
+/// Filters are listed in order of preference for each document type, the
+/// export filters match the extension mappings used by LOK `saveAs`
+#[rustfmt::skip]
+pub const KNOWN_FILTERS: &[KnownFilter] = &[
+    // Writer
+    known(DocumentType::Text, BOTH, "writer8", "writer8", "application/vnd.oasis.opendocument.text", &["odt"]),
+    known(DocumentType::Text, BOTH, "writer8_template", "writer8_template", "application/vnd.oasis.opendocument.text-template", &["ott"]),
+    known(DocumentType::Text, BOTH, "OpenDocument Text Flat XML", "writer_ODT_FlatXML", "application/vnd.oasis.opendocument.text-flat-xml", &["fodt"]),
+    known(DocumentType::Text, BOTH, "MS Word 2007 XML", "writer_MS_Word_2007", "application/vnd.openxmlformats-officedocument.wordprocessingml.document", &["docx"]),
+    known(DocumentType::Text, BOTH, "MS Word 2007 XML VBA", "writer_MS_Word_2007_VBA", "application/vnd.ms-word.document.macroEnabled.12", &["docm"]),
+    known(DocumentType::Text, BOTH, "MS Word 97", "writer_MS_Word_97", "application/msword", &["doc"]),
+    known(DocumentType::Text, BOTH, "Rich Text Format", "writer_Rich_Text_Format", "application/rtf", &["rtf"]),
+    known(DocumentType::Text, BOTH, "Text", "writer_Text", "text/plain", &["txt"]),
+    known(DocumentType::Text, BOTH, "Text (encoded)", "writer_Text_encoded", "text/plain", &["txt"]),
+    known(DocumentType::Text, BOTH, "HTML (StarWriter)", "generic_HTML", "text/html", &["html"]),
+    known(DocumentType::Text, EXPORT, "XHTML Writer File", "XHTML_File", "application/xhtml+xml", &["xhtml"]),
+    known(DocumentType::Text, EXPORT, "EPUB", "writer_EPUB_Document", "application/epub+zip", &["epub"]),
+    known(DocumentType::Text, EXPORT, "writer_pdf_Export", "pdf_Portable_Document_Format", "application/pdf", &["pdf"]),
+    known(DocumentType::Text, EXPORT, "writer_png_Export", "png_Portable_Network_Graphic", "image/png", &["png"]),
+    known(DocumentType::Text, EXPORT, "writer_indexing_export", "writer_indexing_export", "text/xml", &["xml"]),
+    // Calc
+    known(DocumentType::Spreadsheet, BOTH, "calc8", "calc8", "application/vnd.oasis.opendocument.spreadsheet", &["ods"]),
+    known(DocumentType::Spreadsheet, BOTH, "calc8_template", "calc8_template", "application/vnd.oasis.opendocument.spreadsheet-template", &["ots"]),
+    known(DocumentType::Spreadsheet, BOTH, "OpenDocument Spreadsheet Flat XML", "calc_ODS_FlatXML", "application/vnd.oasis.opendocument.spreadsheet-flat-xml", &["fods"]),
+    known(DocumentType::Spreadsheet, BOTH, "Calc MS Excel 2007 XML", "calc_MS_Excel_2007_XML", "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet", &["xlsx"]),
+    known(DocumentType::Spreadsheet, BOTH, "Calc MS Excel 2007 VBA XML", "calc_MS_Excel_2007_VBA_XML", "application/vnd.ms-excel.sheet.macroEnabled.12", &["xlsm"]),
+    known(DocumentType::Spreadsheet, BOTH, "MS Excel 97", "calc_MS_Excel_97", "application/vnd.ms-excel", &["xls"]),
+    known(DocumentType::Spreadsheet, BOTH, "Text - txt - csv (StarCalc)", "calc_Text_txt_csv_StarCalc", "text/csv", &["csv"]),
+    known(DocumentType::Spreadsheet, BOTH, "HTML (StarCalc)", "generic_HTML", "text/html", &["html"]),
+    known(DocumentType::Spreadsheet, EXPORT, "XHTML Calc File", "XHTML_File", "application/xhtml+xml", &["xhtml"]),
+    known(DocumentType::Spreadsheet, EXPORT, "calc_pdf_Export", "pdf_Portable_Document_Format", "application/pdf", &["pdf"]),
+    known(DocumentType::Spreadsheet, EXPORT, "calc_png_Export", "png_Portable_Network_Graphic", "image/png", &["png"]),
+    // Impress
+    known(DocumentType::Presentation, BOTH, "impress8", "impress8", "application/vnd.oasis.opendocument.presentation", &["odp"]),
+    known(DocumentType::Presentation, BOTH, "impress8_template", "impress8_template", "application/vnd.oasis.opendocument.presentation-template", &["otp"]),
+    known(DocumentType::Presentation, BOTH, "OpenDocument Presentation Flat XML", "impress_ODP_FlatXML", "application/vnd.oasis.opendocument.presentation-flat-xml", &["fodp"]),
+    known(DocumentType::Presentation, BOTH, "Impress MS PowerPoint 2007 XML", "impress_MS_PowerPoint_2007_XML", "application/vnd.openxmlformats-officedocument.presentationml.presentation", &["pptx"]),
+    known(DocumentType::Presentation, BOTH, "Impress MS PowerPoint 2007 XML VBA", "impress_MS_PowerPoint_2007_XML_VBA", "application/vnd.ms-powerpoint.presentation.macroEnabled.12", &["pptm"]),
+    known(DocumentType::Presentation, BOTH, "Impress MS PowerPoint 2007 XML Template", "impress_MS_PowerPoint_2007_XML_Template", "application/vnd.ms-powerpoint.template.macroEnabled.12", &["potm"]),
+    known(DocumentType::Presentation, BOTH, "MS PowerPoint 97", "impress_MS_PowerPoint_97", "application/vnd.ms-powerpoint", &["ppt"]),
+    known(DocumentType::Presentation, BOTH, "MS PowerPoint 97 Autoplay", "impress_MS_PowerPoint_97_AutoPlay", "application/vnd.ms-powerpoint", &["pps"]),
+    known(DocumentType::Presentation, BOTH, "MS PowerPoint 97 Vorlage", "impress_MS_PowerPoint_97_Vorlage", "application/vnd.ms-powerpoint", &["pot"]),
+    known(DocumentType::Presentation, EXPORT, "impress_draw8", "draw8", "application/vnd.oasis.opendocument.graphics", &["odg"]),
+    known(DocumentType::Presentation, EXPORT, "impress_html_Export", "graphic_HTML", "text/html", &["html"]),
+    known(DocumentType::Presentation, EXPORT, "XHTML Impress File", "XHTML_File", "application/xhtml+xml", &["xhtml"]),
+    known(DocumentType::Presentation, EXPORT, "impress_pdf_Export", "pdf_Portable_Document_Format", "application/pdf", &["pdf"]),
+    known(DocumentType::Presentation, EXPORT, "impress_svg_Export", "svg_Scalable_Vector_Graphics", "image/svg+xml", &["svg"]),
+    known(DocumentType::Presentation, EXPORT, "impress_png_Export", "png_Portable_Network_Graphic", "image/png", &["png"]),
+    // Draw
+    known(DocumentType::Drawing, BOTH, "draw8", "draw8", "application/vnd.oasis.opendocument.graphics", &["odg"]),
+    known(DocumentType::Drawing, BOTH, "draw_ODG_FlatXML", "draw_ODG_FlatXML", "application/vnd.oasis.opendocument.graphics-flat-xml", &["fodg"]),
+    known(DocumentType::Drawing, IMPORT, "draw_pdf_import", "pdf_Portable_Document_Format", "application/pdf", &["pdf"]),
+    known(DocumentType::Drawing, EXPORT, "draw_html_Export", "graphic_HTML", "text/html", &["html"]),
+    known(DocumentType::Drawing, EXPORT, "XHTML Draw File", "XHTML_File", "application/xhtml+xml", &["xhtml"]),
+    known(DocumentType::Drawing, EXPORT, "draw_pdf_Export", "pdf_Portable_Document_Format", "application/pdf", &["pdf"]),
+    known(DocumentType::Drawing, EXPORT, "draw_svg_Export", "svg_Scalable_Vector_Graphics", "image/svg+xml", &["svg"]),
+    known(DocumentType::Drawing, EXPORT, "draw_png_Export", "png_Portable_Network_Graphic", "image/png", &["png"]),
+];
+
+/// Shorthand for defining a [KnownFilter]
+const fn known(
+    document_type: DocumentType,
+    direction: FilterDirection,
+    name: &'static str,
+    type_name: &'static str,
+    media_type: &'static str,
+    extensions: &'static [&'static str],
+) -> KnownFilter {
+    KnownFilter {
+        name,
+        type_name,
+        document_type,
+        direction,
+        media_type,
+        extensions,
+    }
+}
+
+/// Entry within a [FilterCatalog]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FilterEntry {
+    /// Name of the filter, the type name when the filter is not well-known
+    pub name: String,
+    /// Name of the type detection type the filter handles
+    pub type_name: String,
+    /// Mime type of the filter format
+    pub media_type: String,
+    /// File extensions for the format, empty when the filter is not well-known
+    pub extensions: &'static [&'static str],
+    /// Directions the filter can be used in, empty when the filter is not well-known
+    pub direction: FilterDirection,
+    /// Type of document the filter applies to, [None] when the filter is not well-known
+    pub document_type: Option<DocumentType>,
+}
+
+impl FilterEntry {
+    /// Whether the filter can load documents
+    pub fn is_import(&self) -> bool {
+        self.direction.contains(FilterDirection::IMPORT)
+    }
+
+    /// Whether the filter can save documents
+    pub fn is_export(&self) -> bool {
+        self.direction.contains(FilterDirection::EXPORT)
+    }
+}
+
+impl From<&KnownFilter> for FilterEntry {
+    fn from(value: &KnownFilter) -> Self {
+        Self {
+            name: value.name.to_string(),
+            type_name: value.type_name.to_string(),
+            media_type: value.media_type.to_string(),
+            extensions: value.extensions,
+            direction: value.direction,
+            document_type: Some(value.document_type),
+        }
+    }
+}
+
+/// Catalog of filters with their extensions, directions and document types
+///
+/// Entries are in a deterministic order, well-known filters come first in
+/// order of preference followed by any other filters sorted by name
+#[derive(Debug, Clone)]
+pub struct FilterCatalog {
+    entries: Vec<FilterEntry>,
+}
+
+impl FilterCatalog {
+    /// Creates a catalog of only the built-in well-known filters, assumes
+    /// every filter is available in the LibreOffice install
+    pub fn builtin() -> Self {
+        Self {
+            entries: KNOWN_FILTERS.iter().map(FilterEntry::from).collect(),
+        }
+    }
+
+    /// Creates a catalog of the filters available in `filter_types` (See
+    /// [Office::get_filter_types]) joined with the well-known filter details
+    ///
+    /// The install reports type detection types rather than filters, a
+    /// well-known filter is available when its [KnownFilter::type_name] is
+    /// reported. Types without a well-known filter are listed by type name
+    ///
+    /// [Office::get_filter_types]: crate::Office::get_filter_types
+    pub fn from_filter_types(filter_types: &FilterTypes) -> Self {
+        // Well-known filters that are available
+        let mut entries: Vec<FilterEntry> = KNOWN_FILTERS
+            .iter()
+            .filter_map(|filter| {
+                let filter_type = filter_types.values.get(filter.type_name)?;
+                let mut entry = FilterEntry::from(filter);
+
+                // Prefer the media type reported by the install
+                if !filter_type.media_type.is_empty() {
+                    entry.media_type = filter_type.media_type.clone();
+                }
+
+                Some(entry)
+            })
+            .collect();
+
+        // Remaining filters without well-known details
+        let mut others: Vec<FilterEntry> = filter_types
+            .values
+            .iter()
+            .filter(|(name, _)| !KNOWN_FILTERS.iter().any(|filter| filter.type_name == *name))
+            .map(|(name, filter_type)| FilterEntry {
+                name: name.clone(),
+                type_name: name.clone(),
+                media_type: filter_type.media_type.clone(),
+                extensions: &[],
+                direction: FilterDirection::empty(),
+                document_type: None,
+            })
+            .collect();
+
+        others.sort_by(|a, b| a.name.cmp(&b.name));
+        entries.extend(others);
+
+        Self { entries }
+    }
+
+    /// All entries within the catalog
+    pub fn entries(&self) -> &[FilterEntry] {
+        &self.entries
+    }
+
+    /// Finds the entry for the filter with the provided `name`
+    pub fn get(&self, name: &str) -> Option<&FilterEntry> {
+        self.entries.iter().find(|entry| entry.name == name)
+    }
+
+    /// Finds the preferred filter for exporting a document of `document_type`
+    /// with the provided file `extension`
+    pub fn export_filter_for(
+        &self,
+        document_type: DocumentType,
+        extension: &str,
+    ) -> Option<&FilterEntry> {
+        let extension = extension.trim_start_matches('.');
+
+        self.entries.iter().find(|entry| {
+            entry.is_export()
+                && entry.document_type == Some(document_type)
+                && entry
+                    .extensions
+                    .iter()
+                    .any(|value| value.eq_ignore_ascii_case(extension))
+        })
+    }
+
+    /// Finds the filters able to import the provided `mime` type in order
+    /// of preference
+    pub fn import_filters_for(&self, mime: &str) -> Vec<&FilterEntry> {
+        self.entries
+            .iter()
+            .filter(|entry| entry.is_import() && entry.media_type.eq_ignore_ascii_case(mime))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashMap;
+
+    use super::{FilterCatalog, FilterDirection};
+    use crate::{DocumentType, FilterType, FilterTypes};
+
+    fn filter_types(values: &[(&str, &str)]) -> FilterTypes {
+        FilterTypes {
+            values: values
+                .iter()
+                .map(|(name, media_type)| {
+                    (
+                        name.to_string(),
+                        FilterType {
+                            media_type: media_type.to_string(),
+                        },
+                    )
+                })
+                .collect::<HashMap<_, _>>(),
+        }
+    }
+
+    /// Tests export filters are chosen by document type and extension
+    #[test]
+    fn test_export_filter_for() {
+        let catalog = FilterCatalog::builtin();
+
+        let filter = catalog
+            .export_filter_for(DocumentType::Text, "PDF")
+            .unwrap();
+        assert_eq!(filter.name, "writer_pdf_Export");
+
+        let filter = catalog
+            .export_filter_for(DocumentType::Spreadsheet, ".pdf")
+            .unwrap();
+        assert_eq!(filter.name, "calc_pdf_Export");
+
+        // Preferred filter is used when multiple filters share an extension
+        let filter = catalog
+            .export_filter_for(DocumentType::Text, "txt")
+            .unwrap();
+        assert_eq!(filter.name, "Text");
+
+        // Import only filters are never used for export
+        assert!(catalog
+            .export_filter_for(DocumentType::Drawing, "pdf")
+            .is_some_and(|filter| filter.name == "draw_pdf_Export"));
+
+        assert!(catalog
+            .export_filter_for(DocumentType::Spreadsheet, "docx")
+            .is_none());
+    }
+
+    /// Tests import filters are found by mime type in order
+    #[test]
+    fn test_import_filters_for() {
+        let catalog = FilterCatalog::builtin();
+
+        let names: Vec<&str> = catalog
+            .import_filters_for("text/plain")
+            .into_iter()
+            .map(|entry| entry.name.as_str())
+            .collect();
+        assert_eq!(names, ["Text", "Text (encoded)"]);
+
+        let names: Vec<&str> = catalog
+            .import_filters_for("application/pdf")
+            .into_iter()
+            .map(|entry| entry.name.as_str())
+            .collect();
+        assert_eq!(names, ["draw_pdf_import"]);
+    }
+
+    /// Tests joining the filter types reported by an install
+    #[test]
+    fn test_from_filter_types() {
+        let catalog = FilterCatalog::from_filter_types(&filter_types(&[
+            ("zzz_custom", "application/x-custom"),
+            ("pdf_Portable_Document_Format", "application/pdf"),
+            ("aaa_custom", "application/x-custom"),
+            ("writer8", "application/vnd.oasis.opendocument.text"),
+            (
+                "writer_MS_Word_2007",
+                "application/vnd.openxmlformats-officedocument.wordprocessingml.document",
+            ),
+        ]));
+
+        let names: Vec<&str> = catalog
+            .entries()
+            .iter()
+            .map(|entry| entry.name.as_str())
+            .collect();
+        assert_eq!(
+            names,
+            [
+                "writer8",
+                "MS Word 2007 XML",
+                "writer_pdf_Export",
+                "calc_pdf_Export",
+                "impress_pdf_Export",
+                "draw_pdf_import",
+                "draw_pdf_Export",
+                "aaa_custom",
+                "zzz_custom"
+            ]
+        );
+
+        // Filters sharing a type are available for every document type
+        let filter = catalog
+            .export_filter_for(DocumentType::Spreadsheet, "pdf")
+            .unwrap();
+        assert_eq!(filter.type_name, "pdf_Portable_Document_Format");
+
+        // Filters missing from the install are not available
+        assert!(catalog
+            .export_filter_for(DocumentType::Spreadsheet, "ods")
+            .is_none());
+
+        let custom = catalog.get("aaa_custom").unwrap();
+        assert_eq!(custom.type_name, "aaa_custom");
+        assert_eq!(custom.direction, FilterDirection::empty());
+        assert_eq!(custom.document_type, None);
+    }
+}
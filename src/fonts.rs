@@ -0,0 +1,362 @@
+use std::{
+    cell::RefCell,
+    path::{Path, PathBuf},
+};
+
+use serde::Deserialize;
+
+use crate::{
+    registry::{RegistryItem, RegistryValue},
+    DocUrl, Document, Office, OfficeError, ProfileConfig,
+};
+
+/// File extensions of the font files loaded by [Office::add_font_directory]
+const FONT_EXTENSIONS: &[&str] = &["ttf", "otf", "ttc", "otc", "pfb", "pfa"];
+
+/// Configuration path of the font replacement table
+const SUBSTITUTION_PATH: &str = "/org.openoffice.Office.Common/Font/Substitution";
+
+/// Rule within [FontSubstitutions] replacing one font with another
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FontSubstitution {
+    /// Name of the font to replace (i.e "Calibri")
+    pub font: String,
+    /// Name of the font used in its place (i.e "Carlito")
+    pub replacement: String,
+    /// Whether the font is replaced even when it is available, otherwise
+    /// the font is only replaced when it is missing
+    pub always: bool,
+    /// Whether the replacement only applies to the screen and not to
+    /// printing and exports
+    pub screen_only: bool,
+}
+
+/// Font replacement table applied when the [Office] is created using
+/// [Office::new_with_font_substitutions]:
+///
+/// ```
+/// use libreofficekit::FontSubstitutions;
+///
+/// let substitutions = FontSubstitutions::new()
+///     .replace("Calibri", "Carlito")
+///     .replace_missing("Cambria", "Caladea");
+///
+/// assert_eq!(substitutions.rules().len(), 2);
+/// ```
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct FontSubstitutions {
+    rules: Vec<FontSubstitution>,
+}
+
+impl FontSubstitutions {
+    /// Creates an empty replacement table
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Rules within the table
+    pub fn rules(&self) -> &[FontSubstitution] {
+        &self.rules
+    }
+
+    /// Always replaces `font` with `replacement`, even when `font` is available
+    pub fn replace(self, font: &str, replacement: &str) -> Self {
+        self.rule(FontSubstitution {
+            font: font.to_string(),
+            replacement: replacement.to_string(),
+            always: true,
+            screen_only: false,
+        })
+    }
+
+    /// Replaces `font` with `replacement` only when `font` is missing
+    pub fn replace_missing(self, font: &str, replacement: &str) -> Self {
+        self.rule(FontSubstitution {
+            font: font.to_string(),
+            replacement: replacement.to_string(),
+            always: false,
+            screen_only: false,
+        })
+    }
+
+    /// Adds a `rule` to the table, replacing any existing rule for the same font
+    pub fn rule(mut self, rule: FontSubstitution) -> Self {
+        self.rules
+            .retain(|value| !value.font.eq_ignore_ascii_case(&rule.font));
+        self.rules.push(rule);
+        self
+    }
+
+    /// Configuration items for the replacement table
+    pub(crate) fn registry_items(&self) -> Vec<RegistryItem> {
+        let mut items = vec![RegistryItem::prop(
+            SUBSTITUTION_PATH,
+            "Replacement",
+            RegistryValue::Bool(!self.rules.is_empty()),
+        )];
+
+        items.extend(
+            self.rules
+                .iter()
+                .enumerate()
+                .map(|(index, rule)| RegistryItem::Node {
+                    path: format!("{SUBSTITUTION_PATH}/FontPairs"),
+                    name: format!("_{index}"),
+                    props: vec![
+                        ("Always".to_string(), RegistryValue::Bool(rule.always)),
+                        (
+                            "OnScreenOnly".to_string(),
+                            RegistryValue::Bool(rule.screen_only),
+                        ),
+                        (
+                            "ReplaceFont".to_string(),
+                            RegistryValue::String(rule.font.clone()),
+                        ),
+                        (
+                            "SubstituteFont".to_string(),
+                            RegistryValue::String(rule.replacement.clone()),
+                        ),
+                    ],
+                }),
+        );
+
+        items
+    }
+}
+
+/// Payload of [CallbackType::FontsMissing] (i.e `{ "fontsmissing": [ "Calibri" ] }`)
+///
+/// [CallbackType::FontsMissing]: crate::CallbackType::FontsMissing
+#[derive(Deserialize)]
+struct FontsMissingPayload {
+    #[serde(rename = "fontsmissing")]
+    fonts_missing: Vec<String>,
+}
+
+/// Adds the fonts from a [CallbackType::FontsMissing] `payload` to `missing_fonts`
+///
+/// [CallbackType::FontsMissing]: crate::CallbackType::FontsMissing
+pub(crate) fn record_missing_fonts(missing_fonts: &RefCell<Vec<String>>, payload: &str) {
+    let Ok(payload) = serde_json::from_str::<FontsMissingPayload>(payload) else {
+        return;
+    };
+
+    let mut missing_fonts = missing_fonts.borrow_mut();
+
+    for font in payload.fonts_missing {
+        if !font.is_empty() && !missing_fonts.contains(&font) {
+            missing_fonts.push(font);
+        }
+    }
+}
+
+/// Collects the font files within `directory` and its subdirectories
+fn collect_font_files(directory: &Path, fonts: &mut Vec<PathBuf>) -> std::io::Result<()> {
+    for entry in std::fs::read_dir(directory)? {
+        let path = entry?.path();
+
+        if path.is_dir() {
+            collect_font_files(&path, fonts)?;
+            continue;
+        }
+
+        let is_font = path
+            .extension()
+            .and_then(|value| value.to_str())
+            .is_some_and(|extension| {
+                FONT_EXTENSIONS
+                    .iter()
+                    .any(|value| value.eq_ignore_ascii_case(extension))
+            });
+
+        if is_font {
+            fonts.push(path);
+        }
+    }
+
+    Ok(())
+}
+
+impl Office {
+    /// Creates a new LOK instance from the provided install path with the
+    /// font replacement table set to `substitutions`
+    ///
+    /// The table is written to a new temporary user profile which is removed
+    /// once the instance is dropped, see [Office::new_with_profile_config]
+    pub fn new_with_font_substitutions<P: Into<PathBuf>>(
+        install_path: P,
+        substitutions: &FontSubstitutions,
+    ) -> Result<Office, OfficeError> {
+        let config = ProfileConfig::new().font_substitutions(substitutions.clone());
+        Office::new_with_profile_config(install_path, &config)
+    }
+
+    /// Adds every font file (ttf, otf, ttc, otc, pfb, pfa) within `path` and its
+    /// subdirectories to the available fonts using [Office::add_font]
+    ///
+    /// Provides the paths of the added fonts
+    pub fn add_font_directory(&self, path: &Path) -> Result<Vec<PathBuf>, OfficeError> {
+        let path = std::fs::canonicalize(path).map_err(|_| OfficeError::InvalidPath)?;
+
+        if !path.is_dir() {
+            return Err(OfficeError::InvalidPath);
+        }
+
+        let mut fonts = Vec::new();
+        collect_font_files(&path, &mut fonts)?;
+
+        // Consistent order for reproducible font registration
+        fonts.sort();
+
+        for font in &fonts {
+            let font_path = font.to_str().ok_or(OfficeError::InvalidPath)?;
+            let url = DocUrl::from_absolute_path(font_path)?;
+            self.add_font(&url)?;
+        }
+
+        Ok(fonts)
+    }
+}
+
+impl Document {
+    /// Provides the names of the fonts used by the document that are not
+    /// available and have been substituted
+    ///
+    /// LibreOffice reports missing fonts found while loading through
+    /// [CallbackType::FontsMissing] after the document is loaded, the fonts
+    /// are reported by the time the document has been saved using
+    /// [Document::save_as]
+    ///
+    /// ## Important
+    ///
+    /// Requires LibreOffice 7.5 or newer, older versions do not report
+    /// missing fonts
+    ///
+    /// [CallbackType::FontsMissing]: crate::CallbackType::FontsMissing
+    pub fn missing_fonts(&self) -> Vec<String> {
+        self.events.missing_fonts.borrow().clone()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::cell::RefCell;
+
+    use super::{collect_font_files, record_missing_fonts, FontSubstitution, FontSubstitutions};
+    use crate::{registry::registry_modifications, ProfileConfig};
+
+    /// Tests parsing missing font payloads
+    #[test]
+    fn test_record_missing_fonts() {
+        let missing_fonts = RefCell::new(Vec::new());
+
+        record_missing_fonts(
+            &missing_fonts,
+            r#"{ "fontsmissing": [ "Calibri", "Cambria" ] }"#,
+        );
+        record_missing_fonts(&missing_fonts, r#"{ "fontsmissing": [ "Calibri", "" ] }"#);
+        record_missing_fonts(&missing_fonts, "not json");
+
+        assert_eq!(
+            missing_fonts.into_inner(),
+            vec!["Calibri".to_string(), "Cambria".to_string()]
+        );
+    }
+
+    /// Tests collecting font files from nested directories
+    #[test]
+    fn test_collect_font_files() {
+        let directory = tempfile::tempdir().unwrap();
+        let path = directory.path();
+
+        std::fs::create_dir(path.join("nested")).unwrap();
+        std::fs::write(path.join("Corporate.TTF"), b"").unwrap();
+        std::fs::write(path.join("nested/Corporate-Bold.otf"), b"").unwrap();
+        std::fs::write(path.join("readme.txt"), b"").unwrap();
+
+        let mut fonts = Vec::new();
+        collect_font_files(path, &mut fonts).unwrap();
+        fonts.sort();
+
+        assert_eq!(
+            fonts,
+            vec![
+                path.join("Corporate.TTF"),
+                path.join("nested/Corporate-Bold.otf")
+            ]
+        );
+    }
+
+    /// Tests creating the font replacement table configuration
+    #[test]
+    fn test_font_substitutions() {
+        let substitutions = FontSubstitutions::new()
+            .replace("Calibri", "Arial")
+            .replace_missing("Cambria", "Caladea")
+            .replace("calibri", "Carlito");
+
+        let rules = substitutions.rules();
+        assert_eq!(rules.len(), 2);
+        assert_eq!(rules[0].font, "Cambria");
+        assert!(!rules[0].always);
+        assert_eq!(rules[1].replacement, "Carlito");
+        assert!(rules[1].always);
+
+        let value = registry_modifications(&substitutions.registry_items());
+        assert!(value.contains(concat!(
+            r#"<item oor:path="/org.openoffice.Office.Common/Font/Substitution/FontPairs">"#,
+            r#"<node oor:name="_1" oor:op="replace">"#,
+            r#"<prop oor:name="Always" oor:op="fuse"><value>true</value></prop>"#,
+            r#"<prop oor:name="OnScreenOnly" oor:op="fuse"><value>false</value></prop>"#,
+            r#"<prop oor:name="ReplaceFont" oor:op="fuse"><value>calibri</value></prop>"#,
+            r#"<prop oor:name="SubstituteFont" oor:op="fuse"><value>Carlito</value></prop>"#,
+        )));
+        assert!(value
+            .contains(r#"<prop oor:name="Replacement" oor:op="fuse"><value>true</value></prop>"#));
+    }
+
+    /// Tests rules added directly keep their flags and an empty table
+    /// disables replacement
+    #[test]
+    fn test_font_substitution_rule() {
+        let substitutions = FontSubstitutions::new().rule(FontSubstitution {
+            font: "Segoe UI".to_string(),
+            replacement: "Noto Sans".to_string(),
+            always: false,
+            screen_only: true,
+        });
+
+        let value = registry_modifications(&substitutions.registry_items());
+        assert!(value.contains(concat!(
+            r#"<node oor:name="_0" oor:op="replace">"#,
+            r#"<prop oor:name="Always" oor:op="fuse"><value>false</value></prop>"#,
+            r#"<prop oor:name="OnScreenOnly" oor:op="fuse"><value>true</value></prop>"#,
+        )));
+
+        let value = registry_modifications(&FontSubstitutions::new().registry_items());
+        assert!(value
+            .contains(r#"<prop oor:name="Replacement" oor:op="fuse"><value>false</value></prop>"#));
+        assert!(!value.contains("FontPairs"));
+    }
+
+    /// Tests the replacement table is written into the profile used by
+    /// [crate::Office::new_with_font_substitutions]
+    #[test]
+    fn test_font_substitutions_profile() {
+        let directory = tempfile::tempdir().unwrap();
+        let substitutions = FontSubstitutions::new().replace_missing("Cambria", "Caladea");
+
+        ProfileConfig::new()
+            .font_substitutions(substitutions.clone())
+            .write(directory.path())
+            .unwrap();
+
+        let value =
+            std::fs::read_to_string(directory.path().join("user/registrymodifications.xcu"))
+                .unwrap();
+        assert_eq!(
+            value,
+            registry_modifications(&substitutions.registry_items())
+        );
+    }
+}
@@ -0,0 +1,343 @@
+use std::path::{Path, PathBuf};
+
+use crate::{sys, BuildId, FullVersion, Office, ProductVersion};
+
+/// Environment variables that can point to a LibreOffice install, either the
+/// install directory or its "program" directory
+pub const INSTALL_PATH_ENV_VARS: &[&str] = &["LIBREOFFICE_PATH", "LOK_PATH"];
+
+/// Common system install locations
+const SYSTEM_PATHS: &[&str] = &[
+    "/usr/lib64/libreoffice/program",
+    "/usr/lib/libreoffice/program",
+    "/usr/local/lib64/libreoffice/program",
+    "/usr/local/lib/libreoffice/program",
+];
+
+/// Snap package install location
+const SNAP_PATH: &str = "/snap/libreoffice/current/lib/libreoffice/program";
+
+/// Flatpak install location relative to a flatpak installation directory
+const FLATPAK_APP_PATH: &str =
+    "app/org.libreoffice.LibreOffice/current/active/files/libreoffice/program";
+
+/// Where a LibreOffice install was discovered
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InstallSource {
+    /// One of the [INSTALL_PATH_ENV_VARS] environment variables
+    Environment,
+    /// Common system install location (i.e /usr/lib/libreoffice)
+    System,
+    /// Install within the `/opt` directory
+    Opt,
+    /// Snap package
+    Snap,
+    /// Flatpak package (System or user installation)
+    Flatpak,
+    /// Resolved from the `soffice` executable on the `PATH`
+    Path,
+}
+
+/// LibreOffice install found by [Office::discover_installs]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InstallInfo {
+    /// Path to the "program" directory of the install, provide
+    /// this to [Office::new]
+    pub path: PathBuf,
+    /// Where the install was discovered
+    pub source: InstallSource,
+    /// Version of the install read by [InstallInfo::read_version], [None]
+    /// when the version could not be determined
+    pub version: Option<FullVersion>,
+    /// Build identifier of the install read by [InstallInfo::read_version]
+    pub build_id: Option<BuildId>,
+}
+
+/// Version details of an install read by [InstallInfo::read_version]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InstallVersion {
+    /// Product version of the install, only the major and minor components
+    /// are known when read from "bootstraprc"
+    pub version: FullVersion,
+    /// Build identifier of the install
+    pub build_id: Option<BuildId>,
+}
+
+impl InstallInfo {
+    /// Creates the install info for a "program" directory, [None] when the
+    /// directory does not contain the LibreOffice library
+    fn from_program_path(path: PathBuf, source: InstallSource) -> Option<InstallInfo> {
+        if !sys::has_library(&path) {
+            return None;
+        }
+
+        let (version, build_id) = match Self::read_version(&path) {
+            Some(value) => (Some(value.version), value.build_id),
+            None => (None, None),
+        };
+
+        Some(InstallInfo {
+            path,
+            source,
+            version,
+            build_id,
+        })
+    }
+
+    /// Reads the version of the install at `path` (The "program" directory)
+    /// without loading the library
+    ///
+    /// The full version and build id are read from "versionrc", installs
+    /// without a full version fall back to the "ProductKey" within
+    /// "bootstraprc" (i.e "LibreOffice 7.6")
+    pub fn read_version(path: &Path) -> Option<InstallVersion> {
+        let versionrc = std::fs::read_to_string(path.join("versionrc")).unwrap_or_default();
+
+        let build_id = read_rc_value(&versionrc, "buildid")
+            .filter(|value| !value.is_empty())
+            .map(BuildId::parse);
+
+        let version = read_rc_value(&versionrc, "MsiProductVersion")
+            .and_then(|value| value.parse::<FullVersion>().ok())
+            .or_else(|| {
+                let bootstraprc = std::fs::read_to_string(path.join("bootstraprc")).ok()?;
+                let product_key = read_rc_value(&bootstraprc, "ProductKey")?;
+
+                // Version follows the product name
+                let (_, version) = product_key.rsplit_once(' ')?;
+                let version: ProductVersion = version.parse().ok()?;
+                Some(FullVersion::from(version))
+            })?;
+
+        Some(InstallVersion { version, build_id })
+    }
+}
+
+/// Reads the value for `key` (case insensitive) from the contents of a ".rc"/".ini" file
+fn read_rc_value<'a>(value: &'a str, key: &str) -> Option<&'a str> {
+    value
+        .lines()
+        .filter_map(|line| line.split_once('='))
+        .find(|(line_key, _)| line_key.trim().eq_ignore_ascii_case(key))
+        .map(|(_, value)| value.trim())
+}
+
+/// Policy for choosing between the discovered LibreOffice installs
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InstallPolicy {
+    /// Latest installed version
+    Latest,
+    /// Latest installed version that is at least the provided version
+    Minimum(FullVersion),
+    /// Install with exactly the provided version
+    Exact(FullVersion),
+}
+
+impl InstallPolicy {
+    /// Chooses the install from `installs` matching the policy, installs
+    /// with an unknown version only match [InstallPolicy::Latest] when no
+    /// install has a known version
+    pub fn select<'a>(&self, installs: &'a [InstallInfo]) -> Option<&'a InstallInfo> {
+        let mut versioned = installs
+            .iter()
+            .filter_map(|install| install.version.map(|version| (version, install)));
+
+        match self {
+            InstallPolicy::Latest => versioned
+                // Prefer the earliest discovered install for equal versions
+                .rev()
+                .max_by_key(|(version, _)| *version)
+                .map(|(_, install)| install)
+                .or_else(|| installs.first()),
+            InstallPolicy::Minimum(minimum) => versioned
+                .rev()
+                .filter(|(version, _)| version >= minimum)
+                .max_by_key(|(version, _)| *version)
+                .map(|(_, install)| install),
+            InstallPolicy::Exact(exact) => versioned
+                .find(|(version, _)| version == exact)
+                .map(|(_, install)| install),
+        }
+    }
+}
+
+impl Office {
+    /// Discovers LibreOffice installs from the [INSTALL_PATH_ENV_VARS] environment
+    /// variables, common system locations, `/opt`, snap and flatpak packages and
+    /// the `soffice` executable on the `PATH`
+    ///
+    /// Installs are provided in the order listed above, the version of each
+    /// install is read without loading the library
+    pub fn discover_installs() -> Vec<InstallInfo> {
+        let mut candidates: Vec<(PathBuf, InstallSource)> = Vec::new();
+
+        for name in INSTALL_PATH_ENV_VARS {
+            if let Some(value) = std::env::var_os(name).filter(|value| !value.is_empty()) {
+                let path = PathBuf::from(value);
+
+                // Allow the install directory in place of the program directory
+                let program = path.join("program");
+                let path = if program.is_dir() { program } else { path };
+
+                candidates.push((path, InstallSource::Environment));
+            }
+        }
+
+        candidates.extend(
+            SYSTEM_PATHS
+                .iter()
+                .map(|path| (PathBuf::from(path), InstallSource::System)),
+        );
+
+        if let Ok(installs) = Self::find_opt_installs() {
+            candidates.extend(
+                installs
+                    .into_iter()
+                    .map(|(_, path)| (path, InstallSource::Opt)),
+            );
+        }
+
+        candidates.push((PathBuf::from(SNAP_PATH), InstallSource::Snap));
+
+        let mut flatpak_dirs = vec![PathBuf::from("/var/lib/flatpak")];
+        if let Some(home) = std::env::var_os("HOME") {
+            flatpak_dirs.push(PathBuf::from(home).join(".local/share/flatpak"));
+        }
+
+        candidates.extend(
+            flatpak_dirs
+                .into_iter()
+                .map(|path| (path.join(FLATPAK_APP_PATH), InstallSource::Flatpak)),
+        );
+
+        if let Some(paths) = std::env::var_os("PATH") {
+            candidates.extend(
+                std::env::split_paths(&paths)
+                    // soffice is usually a symlink into the program directory
+                    .filter_map(|path| std::fs::canonicalize(path.join("soffice")).ok())
+                    .filter_map(|path| path.parent().map(Path::to_path_buf))
+                    .map(|path| (path, InstallSource::Path)),
+            );
+        }
+
+        let mut installs: Vec<InstallInfo> = Vec::new();
+
+        for (path, source) in candidates {
+            // Resolve symlinks so the same install is only listed once
+            let Ok(path) = std::fs::canonicalize(&path) else {
+                continue;
+            };
+
+            if installs.iter().any(|install| install.path == path) {
+                continue;
+            }
+
+            if let Some(install) = InstallInfo::from_program_path(path, source) {
+                installs.push(install);
+            }
+        }
+
+        installs
+    }
+
+    /// Finds the install chosen by the provided `policy` from the
+    /// [Office::discover_installs] installs
+    pub fn find_install(policy: InstallPolicy) -> Option<InstallInfo> {
+        let installs = Self::discover_installs();
+        policy.select(&installs).cloned()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::path::PathBuf;
+
+    use super::{InstallInfo, InstallPolicy, InstallSource};
+    use crate::BuildId;
+    use crate::FullVersion;
+
+    fn install(name: &str, version: Option<FullVersion>) -> InstallInfo {
+        InstallInfo {
+            path: PathBuf::from(name),
+            source: InstallSource::System,
+            version,
+            build_id: None,
+        }
+    }
+
+    /// Tests choosing installs using each policy
+    #[test]
+    fn test_install_policy() {
+        let installs = [
+            install("a", Some(FullVersion::new(7, 6, 4, 1))),
+            install("b", None),
+            install("c", Some(FullVersion::new(24, 2, 1, 2))),
+            install("d", Some(FullVersion::new(6, 4, 7, 2))),
+        ];
+
+        let select = |policy: InstallPolicy| {
+            policy
+                .select(&installs)
+                .map(|install| install.path.to_str().unwrap())
+        };
+
+        assert_eq!(select(InstallPolicy::Latest), Some("c"));
+        assert_eq!(
+            select(InstallPolicy::Minimum(FullVersion::new(7, 0, 0, 0))),
+            Some("c")
+        );
+        assert_eq!(
+            select(InstallPolicy::Minimum(FullVersion::new(25, 0, 0, 0))),
+            None
+        );
+        assert_eq!(
+            select(InstallPolicy::Exact(FullVersion::new(6, 4, 7, 2))),
+            Some("d")
+        );
+
+        // Unknown versions are used when no version is known
+        let installs = [install("b", None)];
+        assert_eq!(
+            InstallPolicy::Latest
+                .select(&installs)
+                .map(|install| install.path.clone()),
+            Some(PathBuf::from("b"))
+        );
+    }
+
+    /// Tests reading the install version from versionrc and bootstraprc
+    #[test]
+    fn test_read_version() {
+        let directory = tempfile::tempdir().unwrap();
+        let path = directory.path();
+
+        assert_eq!(InstallInfo::read_version(path), None);
+
+        std::fs::write(
+            path.join("bootstraprc"),
+            "[Bootstrap]\nInstallMode=<installmode>\nProductKey=LibreOffice 7.6\n",
+        )
+        .unwrap();
+        std::fs::write(
+            path.join("versionrc"),
+            "[Version]\nAllLanguages=en-US\nbuildid=e19e193f88cd6c0525a17fb7a176ed8e6a3e2aa1\n",
+        )
+        .unwrap();
+
+        let version = InstallInfo::read_version(path).unwrap();
+        assert_eq!(version.version, FullVersion::new(7, 6, 0, 0));
+        assert_eq!(
+            version.build_id,
+            Some(BuildId::parse("e19e193f88cd6c0525a17fb7a176ed8e6a3e2aa1"))
+        );
+
+        std::fs::write(
+            path.join("versionrc"),
+            "[Version]\nbuildid=e19e193f88cd6c0525a17fb7a176ed8e6a3e2aa1\nMsiProductVersion=7.6.4.1\n",
+        )
+        .unwrap();
+
+        let version = InstallInfo::read_version(path).unwrap();
+        assert_eq!(version.version, FullVersion::new(7, 6, 4, 1));
+    }
+}
@@ -1,12 +1,33 @@
+pub mod batch;
 mod bindings;
+mod callbacks;
+pub mod convert;
+pub mod csv_options;
+pub mod detect;
+pub mod encoding;
 pub mod error;
+pub mod filters;
+pub mod fonts;
+pub mod install;
+pub mod load_options;
+pub mod logging;
+pub mod options;
+pub mod pdf_options;
+pub mod profile;
+mod registry;
+mod stderr;
 mod sys;
+pub mod text_options;
+pub mod trace;
 pub mod urls;
+pub mod version;
 
 use std::{
+    cell::{Cell, RefCell},
     collections::HashMap,
-    ffi::{c_ulonglong, CString},
+    ffi::{c_ulonglong, CStr, CString},
     fmt::Display,
+    ops::Deref,
     os::raw::{c_char, c_int},
     path::{Path, PathBuf},
     ptr::null,
@@ -17,12 +38,29 @@ use std::{
 
 use bitflags::bitflags;
 use num_enum::FromPrimitive;
+use parking_lot::{MappedMutexGuard, Mutex, MutexGuard};
 use serde::{Deserialize, Serialize};
 
+pub use batch::{ConversionReport, ConvertBatch, ConvertJob, Progress};
+pub use convert::{ConvertOptions, ConvertOutput, FormatHint};
+pub use csv_options::{CsvColumnFormat, CsvFilterOptions, CsvSheet};
+pub use detect::DetectedFormat;
+pub use encoding::TextEncoding;
 pub use error::OfficeError;
-use sys::GLOBAL_OFFICE_LOCK;
+pub use filters::{FilterCatalog, FilterDirection, FilterEntry, KnownFilter};
+pub use fonts::{FontSubstitution, FontSubstitutions};
+pub use install::{InstallInfo, InstallPolicy, InstallSource, InstallVersion};
+pub use load_options::LoadOptions;
+pub use logging::CoreLogLine;
+pub use options::{SalLogLevel, SalLogOverride, SalLogRule};
+pub use pdf_options::{PdfALevel, PdfExportOptions};
+pub use profile::{MacroSecurityLevel, ProfileConfig};
+use sys::{GLOBAL_OFFICE_LOCK, LOK_INITIALIZED};
+pub use text_options::{LineEnding, TextExportOptions, TextImportOptions};
 use thiserror::Error;
+pub use trace::TraceFile;
 pub use urls::DocUrl;
+pub use version::{BuildId, FullVersion, KnownIssue, KNOWN_ISSUES};
 
 /// Instance of office.
 ///
@@ -34,6 +72,40 @@ pub use urls::DocUrl;
 #[derive(Clone)]
 pub struct Office {
     raw: Rc<sys::OfficeRaw>,
+    /// Dispatcher for the office callback
+    callbacks: Rc<callbacks::CallbackDispatcher>,
+}
+
+/// Shared office instance, see [Office::shared]
+static SHARED_OFFICE: Mutex<Option<SharedInstance>> = Mutex::new(None);
+
+/// Wrapper allowing the shared office to be stored in a static
+struct SharedInstance(Office);
+
+// SAFETY: The instance is only accessed through the [SHARED_OFFICE] lock
+unsafe impl Send for SharedInstance {}
+
+/// Exclusive access to the shared office instance from [Office::shared]
+pub struct SharedOffice {
+    guard: MappedMutexGuard<'static, Office>,
+}
+
+impl Deref for SharedOffice {
+    type Target = Office;
+
+    fn deref(&self) -> &Self::Target {
+        &self.guard
+    }
+}
+
+impl Drop for SharedOffice {
+    fn drop(&mut self) {
+        // Callbacks and optional features must not carry over to the next user
+        _ = self.guard.clear_callback();
+        _ = self
+            .guard
+            .set_optional_features(OfficeOptionalFeatures::empty());
+    }
 }
 
 /// Instance of [Office] provided to callbacks
@@ -44,6 +116,7 @@ pub struct Office {
 #[derive(Clone)]
 pub struct CallbackOffice {
     raw: Weak<sys::OfficeRaw>,
+    callbacks: Weak<callbacks::CallbackDispatcher>,
 }
 
 impl CallbackOffice {
@@ -51,7 +124,11 @@ impl CallbackOffice {
     pub fn into_office(self) -> Result<Office, OfficeError> {
         // Obtain raw access
         let raw = self.raw.upgrade().ok_or(OfficeError::InstanceDropped)?;
-        Ok(Office { raw })
+        let callbacks = self
+            .callbacks
+            .upgrade()
+            .ok_or(OfficeError::InstanceDropped)?;
+        Ok(Office { raw, callbacks })
     }
 
     /// Sets the password office should try to decrypt the document with.
@@ -89,13 +166,21 @@ impl CallbackOffice {
 
 impl Office {
     /// Creates a new LOK instance from the provided install path
+    ///
+    /// Installs with a version readable through [InstallInfo::read_version]
+    /// older than the minimum supported version (4.3) are refused with
+    /// [OfficeError::UnsupportedVersion] before the install is loaded
     pub fn new<P: Into<PathBuf>>(install_path: P) -> Result<Office, OfficeError> {
-        // Try lock the global office lock
-        if GLOBAL_OFFICE_LOCK.swap(true, Ordering::SeqCst) {
-            return Err(OfficeError::InstanceLock);
-        }
+        Self::init(install_path.into(), None)
+    }
 
-        let mut install_path: PathBuf = install_path.into();
+    /// Creates a new LOK instance using the provided user `profile` when specified
+    fn init(
+        mut install_path: PathBuf,
+        profile: Option<sys::UserProfile>,
+    ) -> Result<Office, OfficeError> {
+        #[cfg(feature = "tracing")]
+        let _span = tracing::info_span!("office_init", path = %install_path.display()).entered();
 
         // Resolve non absolute paths
         if !install_path.is_absolute() {
@@ -103,7 +188,29 @@ impl Office {
                 std::fs::canonicalize(install_path).map_err(|_| OfficeError::InvalidPath)?;
         }
 
-        let raw = match unsafe { sys::OfficeRaw::init(&install_path) } {
+        // Refuse unsupported versions without taking the lock
+        if let Some(install) = InstallInfo::read_version(&install_path) {
+            if !install
+                .version
+                .product_version()
+                .is_document_load_available()
+            {
+                return Err(OfficeError::UnsupportedVersion(install.version));
+            }
+        }
+
+        // Try lock the global office lock
+        if GLOBAL_OFFICE_LOCK.swap(true, Ordering::SeqCst) {
+            return Err(OfficeError::InstanceLock);
+        }
+
+        // LibreOffice does not support initializing again once destroyed
+        if LOK_INITIALIZED.load(Ordering::SeqCst) {
+            GLOBAL_OFFICE_LOCK.store(false, Ordering::SeqCst);
+            return Err(OfficeError::ReinitUnsupported);
+        }
+
+        let raw = match unsafe { sys::OfficeRaw::init(&install_path, profile) } {
             Ok(value) => value,
             Err(err) => {
                 // Unlock the global office lock on init failure
@@ -114,14 +221,61 @@ impl Office {
 
         // Check initialization errors
         if let Some(err) = unsafe { raw.get_error() } {
-            return Err(OfficeError::OfficeError(err));
+            return Err(OfficeError::from_office_message(err, None));
+        }
+
+        let office = Office {
+            raw: Rc::new(raw),
+            callbacks: Default::default(),
+        };
+
+        // Core logs are routed into tracing even without a registered callback,
+        // instances without callback support have no core logs to route
+        #[cfg(feature = "tracing")]
+        {
+            _ = office.callbacks.install(&office.raw);
         }
 
-        Ok(Office { raw: Rc::new(raw) })
+        Ok(office)
+    }
+
+    /// Obtains the process wide shared office instance, creating it using
+    /// [Office::find_install_path] on first use
+    ///
+    /// The shared instance is never dropped so it can be used across tests
+    /// within a single process, where creating an instance per test would
+    /// fail with [OfficeError::ReinitUnsupported]. The returned guard provides
+    /// exclusive access to the instance, other threads will block until it
+    /// is dropped
+    ///
+    /// The callback and optional features are cleared when the guard is dropped
+    ///
+    /// # Safety
+    ///
+    /// The instance is not thread safe, the guard only protects access made
+    /// through it. Clones of the instance, documents loaded from it and
+    /// instances obtained from [CallbackOffice::into_office] must be dropped
+    /// before the guard is dropped
+    pub unsafe fn shared() -> Result<SharedOffice, OfficeError> {
+        let mut shared = SHARED_OFFICE.lock();
+
+        if shared.is_none() {
+            let install_path = Self::find_install_path().ok_or(OfficeError::MissingLibrary)?;
+            *shared = Some(SharedInstance(Office::new(install_path)?));
+        }
+
+        Ok(SharedOffice {
+            guard: MutexGuard::map(shared, |shared| match shared {
+                Some(SharedInstance(office)) => office,
+                None => unreachable!("shared office was initialized above"),
+            }),
+        })
     }
 
     /// Attempts to find an installation path from one of the common system install
     /// locations
+    ///
+    /// See [Office::discover_installs] for searching additional locations
     pub fn find_install_path() -> Option<PathBuf> {
         // Common set of install paths
         const KNOWN_PATHS: &[&str] = &[
@@ -151,8 +305,9 @@ impl Office {
     }
 
     /// Finds all installations of LibreOffice from the `/opt` directory
-    /// provides back a list of the paths along with the version extracted
-    /// from the directory name
+    /// provides back a list of the paths along with the version read from
+    /// the install (See [InstallInfo::read_version]) or extracted from the
+    /// directory name
     pub fn find_opt_installs() -> std::io::Result<Vec<(ProductVersion, PathBuf)>> {
         let opt_path = Path::new("/opt");
         if !opt_path.exists() {
@@ -177,9 +332,6 @@ impl Office {
                 // Only use dirs prefixed with libreoffice
                 let version = dir_name.strip_prefix("libreoffice")?;
 
-                // Only use valid product versions
-                let product_version: ProductVersion = version.parse().ok()?;
-
                 let path = value.path();
                 let path = path.join("program");
 
@@ -188,6 +340,12 @@ impl Office {
                     return None;
                 }
 
+                // Only use valid product versions
+                let product_version: ProductVersion = match InstallInfo::read_version(&path) {
+                    Some(install) => install.version.product_version(),
+                    None => version.parse().ok()?,
+                };
+
                 Some((product_version, path))
             })
             .collect();
@@ -224,6 +382,13 @@ impl Office {
         Ok(value)
     }
 
+    /// Obtains the available filters from LibreOffice joined with the
+    /// details of the well-known filters, see [FilterCatalog]
+    pub fn get_filter_catalog(&self) -> Result<FilterCatalog, OfficeError> {
+        let filter_types = self.get_filter_types()?;
+        Ok(FilterCatalog::from_filter_types(&filter_types))
+    }
+
     /// Obtains the version information from the LibreOffice install
     pub fn get_version_info(&self) -> Result<OfficeVersionInfo, OfficeError> {
         let value = unsafe { self.raw.get_version_info()? };
@@ -262,19 +427,92 @@ impl Office {
 
     /// Loads a document from the provided `url`
     pub fn document_load(&self, url: &DocUrl) -> Result<Document, OfficeError> {
-        let raw = unsafe { self.raw.document_load(url)? };
-        Ok(Document { raw })
+        #[cfg(feature = "tracing")]
+        let _span = tracing::info_span!("document_load", url = %url).entered();
+
+        let (result, stderr) = stderr::capture(|| unsafe { self.raw.document_load(url) });
+        let raw = result.map_err(|err| err.with_stderr(stderr))?;
+        Ok(Document::new(raw, &self.raw))
     }
 
     /// Loads a document with additional options
+    ///
+    /// Returns [OfficeError::InvalidOptionValue] when the `options` cannot
+    /// be represented in the option string (See [LoadOptions::validate])
     pub fn document_load_with_options(
         &self,
         url: &DocUrl,
-        options: &str,
+        options: &LoadOptions,
     ) -> Result<Document, OfficeError> {
+        options.validate()?;
+
+        let options = options.to_string();
+
+        #[cfg(feature = "tracing")]
+        let _span = tracing::info_span!("document_load", url = %url, options).entered();
+
         let options = CString::new(options)?;
-        let raw = unsafe { self.raw.document_load_with_options(url, options.as_ptr())? };
-        Ok(Document { raw })
+        let (result, stderr) = stderr::capture(|| unsafe {
+            self.raw.document_load_with_options(url, options.as_ptr())
+        });
+        let raw = result.map_err(|err| err.with_stderr(stderr))?;
+        Ok(Document::new(raw, &self.raw))
+    }
+
+    /// Loads a document that may be protected by a "password to modify"
+    ///
+    /// When LibreOffice requests the password to modify the provided `password`
+    /// is supplied. When no password is provided or the password is incorrect
+    /// the document is opened read-only instead
+    ///
+    /// ## Important
+    ///
+    /// This enables [OfficeOptionalFeatures::DOCUMENT_PASSWORD_TO_MODIFY] while
+    /// loading, the previously set optional features are restored once loading
+    /// completes
+    pub fn document_load_with_modify_password(
+        &self,
+        url: &DocUrl,
+        password: Option<&str>,
+    ) -> Result<ModifyPasswordDocument, OfficeError> {
+        // Documents without a password to modify are always editable
+        let editable = Rc::new(Cell::new(true));
+
+        // Keep any features enabled by the caller
+        let features = self.optional_features();
+        self.set_optional_features(features | OfficeOptionalFeatures::DOCUMENT_PASSWORD_TO_MODIFY)?;
+
+        let handler = self.add_callback_handler({
+            let editable = editable.clone();
+            let url = url.clone();
+            let mut password = password.map(str::to_string);
+
+            move |office, ty, _| {
+                if let CallbackType::DocumentPasswordModify = ty {
+                    // Password is only provided once, a second request means it was incorrect
+                    match password.take() {
+                        Some(password) => {
+                            _ = office.set_document_password(&url, Some(&password));
+                        }
+                        None => {
+                            // Open the document read-only
+                            editable.set(false);
+                            _ = office.set_document_password(&url, None);
+                        }
+                    }
+                }
+            }
+        })?;
+
+        let result = self.document_load(url);
+
+        drop(handler);
+        self.set_optional_features(features)?;
+
+        Ok(ModifyPasswordDocument {
+            document: result?,
+            editable: editable.get(),
+        })
     }
 
     /// Sends a dialog event
@@ -300,36 +538,35 @@ impl Office {
         Ok(())
     }
 
+    /// Gets the optional features last set with [Office::set_optional_features]
+    pub fn optional_features(&self) -> OfficeOptionalFeatures {
+        OfficeOptionalFeatures::from_bits_retain(self.raw.optional_features())
+    }
+
     /// Registers a callback that will run when Office has some event to inform the
     /// library about (Status indicators, password prompts etc)
-    pub fn register_callback<F>(&self, mut callback: F) -> Result<(), OfficeError>
+    ///
+    /// With the "tracing" feature enabled [CallbackType::CoreLog] payloads are
+    /// emitted as `tracing` events whether or not a callback is registered,
+    /// see [logging::CORE_LOG_TARGET]
+    ///
+    /// [CallbackType::ProfileFrame] payloads are also collected by an active
+    /// trace, see [Office::start_trace]
+    pub fn register_callback<F>(&self, callback: F) -> Result<(), OfficeError>
     where
         F: FnMut(CallbackOffice, CallbackType, *const c_char) + 'static,
     {
-        // Create an office instance to use within the callbacks
-        let callback_office = CallbackOffice {
-            raw: Rc::downgrade(&self.raw),
-        };
-
-        // Create callback wrapper that maps the type
-        let callback = move |ty, payload| {
-            let callback_office = Clone::clone(&callback_office);
-            let ty = CallbackType::from_primitive(ty);
-            callback(callback_office, ty, payload)
-        };
-
-        unsafe {
-            self.raw.register_callback(callback)?;
-        }
+        self.callbacks.install(&self.raw)?;
+        self.callbacks
+            .set_user(Some(Rc::new(RefCell::new(callback))));
 
         Ok(())
     }
 
     /// Clears any current callback registered with [Office::register_callback]
     pub fn clear_callback(&self) -> Result<(), OfficeError> {
-        unsafe {
-            self.raw.clear_callback()?;
-        }
+        self.callbacks.set_user(None);
+        self.callbacks.release(&self.raw)?;
 
         Ok(())
     }
@@ -349,6 +586,9 @@ impl Office {
     }
 
     /// Sets an option in LibreOffice
+    ///
+    /// Prefer the typed setters for the supported options such as
+    /// [Office::set_sal_log_override] and [Office::set_language]
     pub fn set_option(&self, option: &str, value: &str) -> Result<(), OfficeError> {
         let option = CString::new(option)?;
         let value = CString::new(value)?;
@@ -372,29 +612,134 @@ impl Office {
 pub struct Document {
     /// Raw inner document
     raw: sys::DocumentRaw,
+    /// Office instance the document was loaded by, used to obtain errors
+    office: Weak<sys::OfficeRaw>,
+    /// Events reported through the document callback
+    events: Rc<DocumentEvents>,
+}
+
+/// Events collected by the callback registered for each [Document]
+#[derive(Default)]
+struct DocumentEvents {
+    /// Missing fonts reported by [CallbackType::FontsMissing]
+    missing_fonts: RefCell<Vec<String>>,
+    /// [CallbackType::Error] payloads emitted since the last save
+    errors: RefCell<Vec<String>>,
+}
+
+/// Document loaded through [Office::document_load_with_modify_password]
+pub struct ModifyPasswordDocument {
+    /// The loaded document
+    pub document: Document,
+    /// Whether the document was opened for editing, false when the
+    /// password to modify was unknown and the document is read-only
+    pub editable: bool,
 }
 
 impl Document {
+    /// Creates a document for a `raw` document loaded by `office`
+    ///
+    /// A callback is registered for the lifetime of the document as office
+    /// queues some events (i.e [CallbackType::FontsMissing]) when the callback
+    /// is registered and delivers them later
+    fn new(mut raw: sys::DocumentRaw, office: &Rc<sys::OfficeRaw>) -> Document {
+        let events: Rc<DocumentEvents> = Default::default();
+
+        // Older versions without document callbacks report no events
+        _ = unsafe {
+            raw.register_callback({
+                let events = events.clone();
+
+                move |ty, payload| {
+                    if payload.is_null() {
+                        return;
+                    }
+
+                    let payload = CStr::from_ptr(payload).to_string_lossy();
+
+                    match CallbackType::from_primitive(ty) {
+                        CallbackType::Error => {
+                            events.errors.borrow_mut().push(payload.into_owned())
+                        }
+                        CallbackType::FontsMissing => {
+                            fonts::record_missing_fonts(&events.missing_fonts, &payload)
+                        }
+                        _ => {}
+                    }
+                }
+            })
+        };
+
+        Document {
+            raw,
+            office: Rc::downgrade(office),
+            events,
+        }
+    }
+
     /// Saves the document as another format
+    ///
+    /// The `filter` options for PDF output can be created using [PdfExportOptions]
+    ///
+    /// Returns [OfficeError::SaveFailed] when the document could not be saved,
+    /// the reason includes the error reported by office along with any
+    /// [CallbackType::Error] payloads emitted while saving and the error
+    /// includes any lines captured from stderr (See [Office::set_stderr_capture])
     pub fn save_as(
         &mut self,
         url: &DocUrl,
         format: &str,
         filter: Option<&str>,
-    ) -> Result<bool, OfficeError> {
-        let format: CString = CString::new(format)?;
+    ) -> Result<(), OfficeError> {
+        #[cfg(feature = "tracing")]
+        let _span = tracing::info_span!("document_save", url = %url, format).entered();
 
-        let filter = match filter {
-            Some(value) => CString::new(value)?,
-            None => {
-                let result = unsafe { self.raw.save_as(url, format.as_ptr(), null())? };
-                return Ok(result != 0);
-            }
-        };
+        let format_value: CString = CString::new(format)?;
+        let filter = filter.map(CString::new).transpose()?;
+        let filter_ptr = filter.as_ref().map_or(null(), |filter| filter.as_ptr());
+
+        // Only errors emitted while saving are reported
+        self.events.errors.take();
+
+        let (result, stderr) =
+            stderr::capture(|| unsafe { self.raw.save_as(url, format_value.as_ptr(), filter_ptr) });
+
+        let errors = self.events.errors.take();
+
+        if result.map_err(|err| err.with_stderr(stderr.clone()))? != 0 {
+            return Ok(());
+        }
+
+        let mut reasons = Vec::new();
+
+        if let Some(office) = self.office.upgrade() {
+            reasons.extend(unsafe { office.get_error() });
+        }
 
-        let result = unsafe { self.raw.save_as(url, format.as_ptr(), filter.as_ptr())? };
+        reasons.extend(errors);
 
-        Ok(result != 0)
+        Err(OfficeError::SaveFailed {
+            url: url.clone(),
+            format: format.to_string(),
+            reason: reasons.join("\n"),
+            stderr,
+        })
+    }
+
+    /// Saves the loaded document to multiple `targets`, each target is the
+    /// `url` to save to, the `format` to save as and the optional `filter`
+    /// options (See [Document::save_as])
+    ///
+    /// Every target is attempted even when an earlier export fails, the
+    /// result for each target is returned in the same order as `targets`
+    pub fn export_many(
+        &mut self,
+        targets: &[(DocUrl, &str, Option<&str>)],
+    ) -> Vec<Result<(), OfficeError>> {
+        targets
+            .iter()
+            .map(|(url, format, filter)| self.save_as(url, format, *filter))
+            .collect()
     }
 
     /// Obtain the document type
@@ -414,13 +759,18 @@ pub struct FilterTypes {
 
 impl FilterTypes {
     /// Get the filter type name by mime type
+    ///
+    /// When multiple filters share the mime type the first by name is
+    /// used, see [FilterCatalog::import_filters_for] for preferred filters
     pub fn get_by_mime(&self, mime: &str) -> Option<&str> {
         self.values
             .iter()
-            // Find filter with matching media type
-            .find(|(_, value)| value.media_type.eq(mime))
+            // Find filters with matching media type
+            .filter(|(_, value)| value.media_type.eq(mime))
             // Map to only include the key
             .map(|(key, _)| key.as_str())
+            // Use the first by name for a deterministic result
+            .min()
     }
 
     /// Checks if the provided mime type is supported for a filter type
@@ -462,6 +812,7 @@ bitflags! {
     /// deadlock if the client does not support the feature.
     ///
     /// @see [Office::set_optional_features]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
     pub struct OfficeOptionalFeatures: u64 {
         /// Handle `LOK_CALLBACK_DOCUMENT_PASSWORD` by prompting the user for a password.
         ///
@@ -471,6 +822,7 @@ bitflags! {
         /// Handle `LOK_CALLBACK_DOCUMENT_PASSWORD_TO_MODIFY` by prompting the user for a password.
         ///
         /// @see [Office::set_document_password]
+        /// @see [Office::document_load_with_modify_password]
         const DOCUMENT_PASSWORD_TO_MODIFY = 1 << 1;
 
         /// Request to have the part number as a 5th value in the `LOK_CALLBACK_INVALIDATE_TILES` payload.
@@ -705,3 +1057,22 @@ impl Serialize for ProductVersion {
         self.to_string().serialize(serializer)
     }
 }
+
+#[cfg(test)]
+mod test {
+    use std::sync::atomic::Ordering;
+
+    use crate::{sys::LOK_INITIALIZED, Office, OfficeError};
+
+    /// Tests creating an instance after LibreOffice was initialized is refused
+    #[test]
+    fn test_reinit_unsupported() {
+        let directory = tempfile::tempdir().unwrap();
+
+        LOK_INITIALIZED.store(true, Ordering::SeqCst);
+        let result = Office::new(directory.path());
+        LOK_INITIALIZED.store(false, Ordering::SeqCst);
+
+        assert!(matches!(result, Err(OfficeError::ReinitUnsupported)));
+    }
+}
@@ -0,0 +1,248 @@
+use std::fmt;
+
+use crate::{options::is_language_tag, MacroSecurityLevel, OfficeError};
+
+/// Names of the options LibreOffice extracts from the option string, the
+/// remainder of the string is provided to the import filter
+const LOAD_OPTION_NAMES: &[&str] = &[
+    "Language",
+    "Batch",
+    "DeviceFormFactor",
+    "EnableMacrosExecution",
+    "MacroSecurityLevel",
+    "Timezone",
+];
+
+/// Options for loading a document using [Office::document_load_with_options]
+///
+/// Serializes into the comma separated option string understood by
+/// LibreOffice using [ToString::to_string]:
+///
+/// ```
+/// use libreofficekit::{LoadOptions, MacroSecurityLevel};
+///
+/// let options = LoadOptions::new()
+///     .language("en-US")
+///     .batch(true)
+///     .enable_macros(false)
+///     .macro_security_level(MacroSecurityLevel::High);
+///
+/// assert_eq!(
+///     options.to_string(),
+///     "Language=en-US,Batch=true,EnableMacrosExecution=false,MacroSecurityLevel=2"
+/// );
+/// ```
+///
+/// ## Important
+///
+/// The option string has no escaping, [Office::document_load_with_options]
+/// checks the values using [LoadOptions::validate] before loading
+///
+/// LibreOfficeKit only reads the options provided by this builder. The
+/// `ReadOnly`, `Hidden`, `RepairPackage`, `UpdateDocMode` and `FilterName`
+/// media descriptor properties cannot be set when loading, documents are
+/// always loaded hidden and the import filter is always detected
+///
+/// [Office::document_load_with_options]: crate::Office::document_load_with_options
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct LoadOptions {
+    /// Language / locale to load the document with (i.e en-US)
+    language: Option<String>,
+    /// Whether to load in batch mode (No interaction)
+    batch: bool,
+    /// Whether macros within the document may be executed
+    enable_macros: Option<bool>,
+    /// Macro security level used when macros are enabled
+    macro_security_level: Option<MacroSecurityLevel>,
+    /// Override for the import filter options
+    filter_options: Option<String>,
+}
+
+impl LoadOptions {
+    /// Creates a new empty set of load options
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the language / locale to load the document with (i.e en-US)
+    pub fn language<S: Into<String>>(mut self, language: S) -> Self {
+        self.language = Some(language.into());
+        self
+    }
+
+    /// Sets whether the document is loaded in batch mode, in batch mode
+    /// LibreOffice will not attempt to show any interactive dialogs
+    ///
+    /// LibreOffice enables batch mode whenever the option is present, so
+    /// the option is omitted when `batch` is false
+    pub fn batch(mut self, batch: bool) -> Self {
+        self.batch = batch;
+        self
+    }
+
+    /// Sets whether macros within the document may be executed, enabled
+    /// macros are executed according to the macro security level (See
+    /// [LoadOptions::macro_security_level])
+    ///
+    /// LibreOffice never executes macros unless they are enabled
+    pub fn enable_macros(mut self, enable_macros: bool) -> Self {
+        self.enable_macros = Some(enable_macros);
+        self
+    }
+
+    /// Sets the macro security level used when macros are enabled
+    ///
+    /// LibreOffice stores the level in the configuration, so it applies to
+    /// documents loaded afterwards as well
+    pub fn macro_security_level(mut self, level: MacroSecurityLevel) -> Self {
+        self.macro_security_level = Some(level);
+        self
+    }
+
+    /// Overrides the import filter options
+    ///
+    /// LibreOffice passes any unrecognized portion of the options string
+    /// to the filter, so these are always written last and may contain commas
+    pub fn filter_options<S: Into<String>>(mut self, filter_options: S) -> Self {
+        self.filter_options = Some(filter_options.into());
+        self
+    }
+
+    /// Checks the option values can be represented in the option string,
+    /// returns [OfficeError::InvalidOptionValue] for a language that is not
+    /// a valid language tag (i.e contains a comma) or filter options that
+    /// contain an option LibreOffice would extract (i.e ",Language=")
+    pub fn validate(&self) -> Result<(), OfficeError> {
+        if let Some(language) = self
+            .language
+            .as_ref()
+            .filter(|value| !is_language_tag(value))
+        {
+            return Err(OfficeError::InvalidOptionValue {
+                option: "language",
+                value: language.clone(),
+            });
+        }
+
+        if let Some(filter_options) = self
+            .filter_options
+            .as_ref()
+            .filter(|value| contains_load_option(value))
+        {
+            return Err(OfficeError::InvalidOptionValue {
+                option: "filter_options",
+                value: filter_options.clone(),
+            });
+        }
+
+        Ok(())
+    }
+}
+
+/// Checks if `value` contains an option LibreOffice extracts from the
+/// option string, options are extracted from the start or after a comma
+fn contains_load_option(value: &str) -> bool {
+    LOAD_OPTION_NAMES.iter().any(|name| {
+        let option = format!("{name}=");
+        value.starts_with(&option) || value.contains(&format!(",{option}"))
+    })
+}
+
+impl fmt::Display for LoadOptions {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut parts: Vec<String> = Vec::new();
+
+        if let Some(language) = &self.language {
+            parts.push(format!("Language={language}"));
+        }
+
+        if self.batch {
+            parts.push("Batch=true".to_string());
+        }
+
+        if let Some(enable_macros) = self.enable_macros {
+            parts.push(format!("EnableMacrosExecution={enable_macros}"));
+        }
+
+        if let Some(level) = self.macro_security_level {
+            parts.push(format!("MacroSecurityLevel={}", level as i64));
+        }
+
+        // Remaining unrecognized options are provided to the filter
+        if let Some(filter_options) = &self.filter_options {
+            parts.push(filter_options.clone());
+        }
+
+        f.write_str(&parts.join(","))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::LoadOptions;
+    use crate::{MacroSecurityLevel, OfficeError};
+
+    /// Tests empty options serialize to an empty string
+    #[test]
+    fn test_empty() {
+        assert_eq!(LoadOptions::new().to_string(), "");
+    }
+
+    /// Tests all options serialize in the expected order
+    #[test]
+    fn test_all_options() {
+        let options = LoadOptions::new()
+            .filter_options("44,34,76")
+            .language("de-DE")
+            .batch(true)
+            .enable_macros(true)
+            .macro_security_level(MacroSecurityLevel::VeryHigh);
+
+        assert_eq!(
+            options.to_string(),
+            "Language=de-DE,Batch=true,EnableMacrosExecution=true,MacroSecurityLevel=3,44,34,76"
+        );
+    }
+
+    /// Tests batch mode is omitted when disabled as any value enables it
+    #[test]
+    fn test_batch_disabled() {
+        assert_eq!(LoadOptions::new().batch(false).to_string(), "");
+        assert_eq!(
+            LoadOptions::new()
+                .batch(true)
+                .batch(false)
+                .enable_macros(false)
+                .to_string(),
+            "EnableMacrosExecution=false"
+        );
+    }
+
+    /// Tests values containing separators or extracted options are rejected
+    #[test]
+    fn test_validate() {
+        assert!(LoadOptions::new()
+            .language("en-US")
+            .filter_options("44,34,76")
+            .validate()
+            .is_ok());
+
+        assert!(matches!(
+            LoadOptions::new().language("en-US,Batch=true").validate(),
+            Err(OfficeError::InvalidOptionValue {
+                option: "language",
+                ..
+            })
+        ));
+
+        for filter_options in ["44,34,76,Language=de-DE", "Batch=true,44"] {
+            assert!(matches!(
+                LoadOptions::new().filter_options(filter_options).validate(),
+                Err(OfficeError::InvalidOptionValue {
+                    option: "filter_options",
+                    ..
+                })
+            ));
+        }
+    }
+}
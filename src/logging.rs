@@ -0,0 +1,180 @@
+use crate::SalLogLevel;
+
+/// Target of the `tracing` events created from LibreOffice core logs, the
+/// log area (i.e "sc.core") is provided as the "area" field
+pub const CORE_LOG_TARGET: &str = "libreoffice";
+
+/// Log line provided by [CallbackType::CoreLog] in the `SAL_LOG` format
+/// (i.e "warn:vcl:1234:1234:vcl/source/app/svapp.cxx:123: message")
+///
+/// [CallbackType::CoreLog]: crate::CallbackType::CoreLog
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CoreLogLine<'a> {
+    /// Level of the log
+    pub level: SalLogLevel,
+    /// Area the log originated from (i.e "sc.core")
+    pub area: &'a str,
+    /// Source location of the log (i.e "vcl/source/app/svapp.cxx:123")
+    pub location: Option<&'a str>,
+    /// The log message
+    pub message: &'a str,
+}
+
+impl<'a> CoreLogLine<'a> {
+    /// Parses a log line, [None] if the line is not in the `SAL_LOG` format
+    pub fn parse(payload: &'a str) -> Option<Self> {
+        let mut parts = payload.trim_end().splitn(5, ':');
+
+        let level = match parts.next()? {
+            "info" => SalLogLevel::Info,
+            "warn" => SalLogLevel::Warn,
+            "debug" => SalLogLevel::Debug,
+            "time" => SalLogLevel::Time,
+            _ => return None,
+        };
+
+        let area = parts.next()?;
+
+        // Process and thread ids
+        let _pid = parts.next()?;
+        let _thread = parts.next()?;
+
+        let rest = parts.next()?;
+
+        let (location, message) = match rest.split_once(": ") {
+            // Location ends with the line number
+            Some((location, message))
+                if location
+                    .rsplit_once(':')
+                    .is_some_and(|(_, line)| line.chars().all(|c| c.is_ascii_digit())) =>
+            {
+                (Some(location), message)
+            }
+            _ => (None, rest.trim_start()),
+        };
+
+        Some(Self {
+            level,
+            area,
+            location,
+            message,
+        })
+    }
+}
+
+#[cfg(feature = "tracing")]
+mod tracing_support {
+    use tracing::Level;
+
+    use super::{CoreLogLine, CORE_LOG_TARGET};
+    use crate::{SalLogLevel, SalLogOverride};
+
+    impl From<SalLogLevel> for Level {
+        fn from(value: SalLogLevel) -> Self {
+            match value {
+                SalLogLevel::Warn => Level::WARN,
+                SalLogLevel::Info => Level::INFO,
+                SalLogLevel::Debug => Level::DEBUG,
+                SalLogLevel::Time => Level::TRACE,
+            }
+        }
+    }
+
+    impl SalLogOverride {
+        /// Creates an override enabling all LibreOffice logs at or above the
+        /// provided `tracing` level, for use with [Office::set_sal_log_override]
+        ///
+        /// [Office::set_sal_log_override]: crate::Office::set_sal_log_override
+        pub fn for_level(level: Level) -> Self {
+            let mut value = SalLogOverride::new();
+
+            for sal_level in [
+                SalLogLevel::Warn,
+                SalLogLevel::Info,
+                SalLogLevel::Debug,
+                SalLogLevel::Time,
+            ] {
+                if Level::from(sal_level) <= level {
+                    value = value
+                        .enable(sal_level, None)
+                        .expect("rules without an area are valid");
+                }
+            }
+
+            value
+        }
+    }
+
+    /// Emits a `tracing` event for a [CallbackType::CoreLog] payload
+    ///
+    /// [CallbackType::CoreLog]: crate::CallbackType::CoreLog
+    pub(crate) fn emit_core_log(payload: &str) {
+        let Some(line) = CoreLogLine::parse(payload) else {
+            tracing::info!(target: CORE_LOG_TARGET, "{}", payload.trim_end());
+            return;
+        };
+
+        macro_rules! emit {
+            ($level:expr) => {
+                tracing::event!(
+                    target: CORE_LOG_TARGET,
+                    $level,
+                    area = line.area,
+                    location = line.location,
+                    "{}",
+                    line.message
+                )
+            };
+        }
+
+        match line.level {
+            SalLogLevel::Warn => emit!(Level::WARN),
+            SalLogLevel::Info => emit!(Level::INFO),
+            SalLogLevel::Debug => emit!(Level::DEBUG),
+            SalLogLevel::Time => emit!(Level::TRACE),
+        }
+    }
+}
+
+#[cfg(feature = "tracing")]
+pub(crate) use tracing_support::emit_core_log;
+
+#[cfg(test)]
+mod test {
+    use super::CoreLogLine;
+    use crate::SalLogLevel;
+
+    /// Tests parsing core log lines
+    #[test]
+    fn test_parse_core_log() {
+        let line =
+            CoreLogLine::parse("warn:vcl:1234:5678:vcl/source/app/svapp.cxx:123: missing font\n")
+                .unwrap();
+
+        assert_eq!(line.level, SalLogLevel::Warn);
+        assert_eq!(line.area, "vcl");
+        assert_eq!(line.location, Some("vcl/source/app/svapp.cxx:123"));
+        assert_eq!(line.message, "missing font");
+
+        let line = CoreLogLine::parse("info:sc.core:1:2:loaded: 2 sheets").unwrap();
+        assert_eq!(line.level, SalLogLevel::Info);
+        assert_eq!(line.location, None);
+        assert_eq!(line.message, "loaded: 2 sheets");
+
+        assert!(CoreLogLine::parse("not a log line").is_none());
+    }
+
+    /// Tests creating log overrides from tracing levels
+    #[cfg(feature = "tracing")]
+    #[test]
+    fn test_sal_log_override_for_level() {
+        use crate::SalLogOverride;
+        use tracing::Level;
+
+        assert_eq!(SalLogOverride::for_level(Level::WARN).to_string(), "+WARN");
+        assert_eq!(
+            SalLogOverride::for_level(Level::DEBUG).to_string(),
+            "+WARN+INFO+DEBUG"
+        );
+    }
+}
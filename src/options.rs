@@ -0,0 +1,274 @@
+use std::{fmt, str::FromStr};
+
+use crate::{DocUrl, Office, OfficeError};
+
+/// Log level for [SalLogOverride] rules, matching the `SAL_LOG` levels
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SalLogLevel {
+    Info,
+    Warn,
+    Debug,
+    Time,
+}
+
+impl SalLogLevel {
+    fn as_str(&self) -> &'static str {
+        match self {
+            SalLogLevel::Info => "INFO",
+            SalLogLevel::Warn => "WARN",
+            SalLogLevel::Debug => "DEBUG",
+            SalLogLevel::Time => "TIME",
+        }
+    }
+}
+
+/// Single rule of a [SalLogOverride]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SalLogRule {
+    /// Whether matching logs are enabled (+) or disabled (-)
+    pub enabled: bool,
+    /// Level of the logs the rule applies to
+    pub level: SalLogLevel,
+    /// Area the rule is limited to (i.e "sc.core"), [None] for all areas
+    pub area: Option<String>,
+}
+
+/// Log filter for the "sallogoverride" option using the `SAL_LOG` syntax,
+/// rules are applied in order with later rules taking priority:
+///
+/// ```
+/// use libreofficekit::{SalLogLevel, SalLogOverride};
+///
+/// let value = SalLogOverride::new()
+///     .enable(SalLogLevel::Warn, None)?
+///     .disable(SalLogLevel::Warn, Some("vcl"))?;
+///
+/// assert_eq!(value.to_string(), "+WARN-WARN.vcl");
+/// # Ok::<(), libreofficekit::OfficeError>(())
+/// ```
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct SalLogOverride {
+    rules: Vec<SalLogRule>,
+}
+
+impl SalLogOverride {
+    /// Creates an empty override, which restores the default logging
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Rules within the override
+    pub fn rules(&self) -> &[SalLogRule] {
+        &self.rules
+    }
+
+    /// Enables logs of `level` for the `area` (All areas when [None])
+    pub fn enable(self, level: SalLogLevel, area: Option<&str>) -> Result<Self, OfficeError> {
+        self.rule(true, level, area)
+    }
+
+    /// Disables logs of `level` for the `area` (All areas when [None])
+    pub fn disable(self, level: SalLogLevel, area: Option<&str>) -> Result<Self, OfficeError> {
+        self.rule(false, level, area)
+    }
+
+    fn rule(
+        mut self,
+        enabled: bool,
+        level: SalLogLevel,
+        area: Option<&str>,
+    ) -> Result<Self, OfficeError> {
+        if let Some(area) = area {
+            // Areas are dot separated identifiers (i.e "sc.core")
+            let valid = area.split('.').all(|part| {
+                !part.is_empty() && part.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+            });
+
+            if !valid {
+                return Err(OfficeError::InvalidOptionValue {
+                    option: "sallogoverride",
+                    value: area.to_string(),
+                });
+            }
+        }
+
+        self.rules.push(SalLogRule {
+            enabled,
+            level,
+            area: area.map(str::to_string),
+        });
+
+        Ok(self)
+    }
+}
+
+impl fmt::Display for SalLogOverride {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for rule in &self.rules {
+            f.write_str(if rule.enabled { "+" } else { "-" })?;
+            f.write_str(rule.level.as_str())?;
+
+            if let Some(area) = &rule.area {
+                write!(f, ".{area}")?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl FromStr for SalLogOverride {
+    type Err = OfficeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || OfficeError::InvalidOptionValue {
+            option: "sallogoverride",
+            value: s.to_string(),
+        };
+
+        let mut value = Self::new();
+        let mut rest = s;
+
+        while !rest.is_empty() {
+            let enabled = match rest.as_bytes()[0] {
+                b'+' => true,
+                b'-' => false,
+                _ => return Err(invalid()),
+            };
+
+            // Rule continues until the next +/-
+            let end = rest[1..]
+                .find(['+', '-'])
+                .map_or(rest.len(), |index| index + 1);
+            let rule = &rest[1..end];
+            rest = &rest[end..];
+
+            let (level, area) = match rule.split_once('.') {
+                Some((level, area)) => (level, Some(area)),
+                None => (rule, None),
+            };
+
+            let level = match level {
+                "INFO" => SalLogLevel::Info,
+                "WARN" => SalLogLevel::Warn,
+                "DEBUG" => SalLogLevel::Debug,
+                "TIME" => SalLogLevel::Time,
+                _ => return Err(invalid()),
+            };
+
+            value = value.rule(enabled, level, area).map_err(|_| invalid())?;
+        }
+
+        Ok(value)
+    }
+}
+
+/// Checks the `value` is a BCP 47 style language tag (i.e "en-US")
+pub(crate) fn is_language_tag(value: &str) -> bool {
+    let mut parts = value.split('-');
+
+    let language = parts.next().unwrap_or_default();
+    if !(2..=3).contains(&language.len()) || !language.chars().all(|c| c.is_ascii_alphabetic()) {
+        return false;
+    }
+
+    parts.all(|part| {
+        (1..=8).contains(&part.len()) && part.chars().all(|c| c.is_ascii_alphanumeric())
+    })
+}
+
+/// Value for options that start and stop recording
+fn recording_value(enabled: bool) -> &'static str {
+    if enabled {
+        "start"
+    } else {
+        "stop"
+    }
+}
+
+impl Office {
+    /// Starts or stops recording trace events ("traceeventrecording"),
+    /// recorded events are provided through [CallbackType::ProfileFrame]
+    ///
+    /// [CallbackType::ProfileFrame]: crate::CallbackType::ProfileFrame
+    pub fn set_trace_event_recording(&self, enabled: bool) -> Result<(), OfficeError> {
+        self.set_option("traceeventrecording", recording_value(enabled))
+    }
+
+    /// Starts or stops recording profile zones ("profilezonerecording")
+    pub fn set_profile_zone_recording(&self, enabled: bool) -> Result<(), OfficeError> {
+        self.set_option("profilezonerecording", recording_value(enabled))
+    }
+
+    /// Overrides which LibreOffice logs are written ("sallogoverride"), an
+    /// empty [SalLogOverride] restores the default logging
+    pub fn set_sal_log_override(&self, value: &SalLogOverride) -> Result<(), OfficeError> {
+        self.set_option("sallogoverride", &value.to_string())
+    }
+
+    /// Adds the font at `url` (A local file URL) to the available fonts ("addfont")
+    pub fn add_font(&self, url: &DocUrl) -> Result<(), OfficeError> {
+        let path = url.to_file_path().ok_or(OfficeError::InvalidPath)?;
+
+        if !path.exists() {
+            return Err(OfficeError::InvalidPath);
+        }
+
+        self.set_option("addfont", &url.to_string())
+    }
+
+    /// Sets whether fonts installed on the local system can be used ("allowlocalfonts")
+    pub fn set_allow_local_fonts(&self, allow: bool) -> Result<(), OfficeError> {
+        self.set_option("allowlocalfonts", if allow { "true" } else { "false" })
+    }
+
+    /// Sets the language and locale used by LibreOffice ("language") using
+    /// a language tag (i.e "en-US")
+    pub fn set_language(&self, tag: &str) -> Result<(), OfficeError> {
+        if !is_language_tag(tag) {
+            return Err(OfficeError::InvalidOptionValue {
+                option: "language",
+                value: tag.to_string(),
+            });
+        }
+
+        self.set_option("language", tag)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{is_language_tag, SalLogLevel, SalLogOverride};
+
+    /// Tests building and parsing log overrides
+    #[test]
+    fn test_sal_log_override() {
+        let value = SalLogOverride::new()
+            .enable(SalLogLevel::Info, Some("sc.core"))
+            .unwrap()
+            .disable(SalLogLevel::Debug, None)
+            .unwrap();
+
+        assert_eq!(value.to_string(), "+INFO.sc.core-DEBUG");
+        assert_eq!(value.to_string().parse::<SalLogOverride>().unwrap(), value);
+
+        assert_eq!("".parse::<SalLogOverride>().unwrap(), SalLogOverride::new());
+        assert!("+ERROR".parse::<SalLogOverride>().is_err());
+        assert!("WARN".parse::<SalLogOverride>().is_err());
+        assert!("+WARN.".parse::<SalLogOverride>().is_err());
+        assert!(SalLogOverride::new()
+            .enable(SalLogLevel::Warn, Some("sc core"))
+            .is_err());
+    }
+
+    /// Tests validating language tags
+    #[test]
+    fn test_language_tag() {
+        assert!(is_language_tag("en"));
+        assert!(is_language_tag("en-US"));
+        assert!(is_language_tag("zh-Hans-CN"));
+        assert!(!is_language_tag("english"));
+        assert!(!is_language_tag("en_US"));
+        assert!(!is_language_tag("en-"));
+    }
+}
@@ -0,0 +1,296 @@
+use std::fmt;
+
+use serde_json::{Map, Value};
+
+/// Options for the PDF export filter, provided as the `filter` argument
+/// of [Document::save_as] using [ToString::to_string]
+///
+/// Serializes into the JSON `FilterData` format accepted by LibreOffice
+/// where each property specifies its UNO type and value:
+///
+/// ```
+/// use libreofficekit::{PdfALevel, PdfExportOptions};
+///
+/// let options = PdfExportOptions::new().pdfa(PdfALevel::PdfA2b);
+///
+/// assert_eq!(
+///     options.to_string(),
+///     r#"{"SelectPdfVersion":{"type":"long","value":"2"}}"#
+/// );
+/// ```
+///
+/// JSON filter options require LibreOffice 7.4 or newer
+///
+/// [Document::save_as]: crate::Document::save_as
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct PdfExportOptions {
+    /// PDF/A conformance level
+    pdfa: Option<PdfALevel>,
+    /// Whether to produce PDF/UA compliant output
+    pdfua: Option<bool>,
+    /// Range of pages to export (i.e "1-3;5")
+    page_range: Option<String>,
+    /// JPEG image quality between 1 and 100
+    image_quality: Option<u8>,
+    /// Maximum image resolution in DPI, images are downsampled when set
+    max_image_resolution: Option<u32>,
+    /// Whether to use lossless image compression
+    lossless_images: Option<bool>,
+    /// Whether to export bookmarks / the outline
+    bookmarks: Option<bool>,
+    /// Whether to export comments as PDF annotations
+    comments_as_annotations: Option<bool>,
+    /// Text to draw as a watermark on every page
+    watermark: Option<String>,
+    /// Password required to open the document
+    open_password: Option<String>,
+    /// Password required to change the document permissions
+    permission_password: Option<String>,
+    /// Whether to export each Calc sheet as a single page
+    single_page_sheets: Option<bool>,
+}
+
+impl PdfExportOptions {
+    /// Creates a new empty set of PDF export options
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the PDF/A conformance level of the output
+    pub fn pdfa(mut self, level: PdfALevel) -> Self {
+        self.pdfa = Some(level);
+        self
+    }
+
+    /// Sets whether the output should be PDF/UA (Universal Accessibility)
+    /// compliant, enabling this also produces a tagged PDF
+    pub fn pdfua(mut self, pdfua: bool) -> Self {
+        self.pdfua = Some(pdfua);
+        self
+    }
+
+    /// Sets the range of pages to export (i.e "1-3;5")
+    pub fn page_range<S: Into<String>>(mut self, page_range: S) -> Self {
+        self.page_range = Some(page_range.into());
+        self
+    }
+
+    /// Sets the JPEG image quality, values are clamped between 1 and 100
+    pub fn image_quality(mut self, quality: u8) -> Self {
+        self.image_quality = Some(quality.clamp(1, 100));
+        self
+    }
+
+    /// Sets the maximum image resolution in DPI, images with a higher
+    /// resolution are downsampled
+    pub fn max_image_resolution(mut self, dpi: u32) -> Self {
+        self.max_image_resolution = Some(dpi);
+        self
+    }
+
+    /// Sets whether images use lossless compression instead of JPEG
+    pub fn lossless_images(mut self, lossless: bool) -> Self {
+        self.lossless_images = Some(lossless);
+        self
+    }
+
+    /// Sets whether bookmarks (The document outline) are exported
+    pub fn bookmarks(mut self, bookmarks: bool) -> Self {
+        self.bookmarks = Some(bookmarks);
+        self
+    }
+
+    /// Sets whether comments are exported as PDF annotations
+    pub fn comments_as_annotations(mut self, comments: bool) -> Self {
+        self.comments_as_annotations = Some(comments);
+        self
+    }
+
+    /// Sets the text drawn as a watermark on every page
+    pub fn watermark<S: Into<String>>(mut self, watermark: S) -> Self {
+        self.watermark = Some(watermark.into());
+        self
+    }
+
+    /// Encrypts the PDF requiring the provided password to open it
+    pub fn open_password<S: Into<String>>(mut self, password: S) -> Self {
+        self.open_password = Some(password.into());
+        self
+    }
+
+    /// Restricts the PDF permissions requiring the provided password to change them
+    pub fn permission_password<S: Into<String>>(mut self, password: S) -> Self {
+        self.permission_password = Some(password.into());
+        self
+    }
+
+    /// Sets whether each Calc sheet is exported as a single page
+    pub fn single_page_sheets(mut self, single_page_sheets: bool) -> Self {
+        self.single_page_sheets = Some(single_page_sheets);
+        self
+    }
+
+    /// Creates the filter data properties for the options
+    fn filter_data(&self) -> Map<String, Value> {
+        let mut data = FilterData::default();
+
+        if let Some(level) = self.pdfa {
+            data.long("SelectPdfVersion", level as i32);
+        }
+
+        if let Some(pdfua) = self.pdfua {
+            data.boolean("PDFUACompliance", pdfua);
+
+            // PDF/UA requires a tagged PDF
+            if pdfua {
+                data.boolean("UseTaggedPDF", true);
+            }
+        }
+
+        if let Some(page_range) = &self.page_range {
+            data.string("PageRange", page_range);
+        }
+
+        if let Some(quality) = self.image_quality {
+            data.long("Quality", quality);
+        }
+
+        if let Some(dpi) = self.max_image_resolution {
+            data.boolean("ReduceImageResolution", true);
+            data.long("MaxImageResolution", dpi);
+        }
+
+        if let Some(lossless) = self.lossless_images {
+            data.boolean("UseLosslessCompression", lossless);
+        }
+
+        if let Some(bookmarks) = self.bookmarks {
+            data.boolean("ExportBookmarks", bookmarks);
+        }
+
+        if let Some(comments) = self.comments_as_annotations {
+            data.boolean("ExportNotes", comments);
+        }
+
+        if let Some(watermark) = &self.watermark {
+            data.string("Watermark", watermark);
+        }
+
+        if let Some(password) = &self.open_password {
+            data.boolean("EncryptFile", true);
+            data.string("DocumentOpenPassword", password);
+        }
+
+        if let Some(password) = &self.permission_password {
+            data.boolean("RestrictPermissions", true);
+            data.string("PermissionPassword", password);
+        }
+
+        if let Some(single_page_sheets) = self.single_page_sheets {
+            data.boolean("SinglePageSheets", single_page_sheets);
+        }
+
+        data.0
+    }
+}
+
+impl fmt::Display for PdfExportOptions {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let value = Value::Object(self.filter_data());
+        write!(f, "{value}")
+    }
+}
+
+/// PDF/A conformance levels (`SelectPdfVersion` filter property)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(i32)]
+pub enum PdfALevel {
+    /// PDF/A-1b (ISO 19005-1)
+    PdfA1b = 1,
+    /// PDF/A-2b (ISO 19005-2)
+    PdfA2b = 2,
+    /// PDF/A-3b (ISO 19005-3)
+    PdfA3b = 3,
+    /// PDF/A-4 (ISO 19005-4), requires LibreOffice 7.6 or newer
+    PdfA4 = 4,
+}
+
+/// Builder for typed JSON filter data properties
+#[derive(Default)]
+struct FilterData(Map<String, Value>);
+
+impl FilterData {
+    fn insert(&mut self, name: &str, ty: &str, value: String) {
+        let mut property = Map::new();
+        property.insert("type".to_string(), Value::String(ty.to_string()));
+        property.insert("value".to_string(), Value::String(value));
+        self.0.insert(name.to_string(), Value::Object(property));
+    }
+
+    fn string(&mut self, name: &str, value: &str) {
+        self.insert(name, "string", value.to_string());
+    }
+
+    fn boolean(&mut self, name: &str, value: bool) {
+        self.insert(name, "boolean", value.to_string());
+    }
+
+    fn long<V: fmt::Display>(&mut self, name: &str, value: V) {
+        self.insert(name, "long", value.to_string());
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use serde_json::{json, Value};
+
+    use super::{PdfALevel, PdfExportOptions};
+
+    /// Tests empty options serialize to an empty JSON object
+    #[test]
+    fn test_empty() {
+        assert_eq!(PdfExportOptions::new().to_string(), "{}");
+    }
+
+    /// Tests options serialize to typed filter data properties
+    #[test]
+    fn test_all_options() {
+        let options = PdfExportOptions::new()
+            .pdfa(PdfALevel::PdfA3b)
+            .pdfua(true)
+            .page_range("1-3;5")
+            .image_quality(150)
+            .max_image_resolution(300)
+            .lossless_images(false)
+            .bookmarks(true)
+            .comments_as_annotations(true)
+            .watermark("Draft \"1\"")
+            .open_password("open")
+            .permission_password("permission")
+            .single_page_sheets(true);
+
+        let value: Value = serde_json::from_str(&options.to_string()).unwrap();
+
+        assert_eq!(
+            value,
+            json!({
+                "SelectPdfVersion": { "type": "long", "value": "3" },
+                "PDFUACompliance": { "type": "boolean", "value": "true" },
+                "UseTaggedPDF": { "type": "boolean", "value": "true" },
+                "PageRange": { "type": "string", "value": "1-3;5" },
+                "Quality": { "type": "long", "value": "100" },
+                "ReduceImageResolution": { "type": "boolean", "value": "true" },
+                "MaxImageResolution": { "type": "long", "value": "300" },
+                "UseLosslessCompression": { "type": "boolean", "value": "false" },
+                "ExportBookmarks": { "type": "boolean", "value": "true" },
+                "ExportNotes": { "type": "boolean", "value": "true" },
+                "Watermark": { "type": "string", "value": "Draft \"1\"" },
+                "EncryptFile": { "type": "boolean", "value": "true" },
+                "DocumentOpenPassword": { "type": "string", "value": "open" },
+                "RestrictPermissions": { "type": "boolean", "value": "true" },
+                "PermissionPassword": { "type": "string", "value": "permission" },
+                "SinglePageSheets": { "type": "boolean", "value": "true" },
+            })
+        );
+    }
+}
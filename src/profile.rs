@@ -0,0 +1,385 @@
+use std::{
+    fmt,
+    path::{Path, PathBuf},
+};
+
+use crate::{
+    options::is_language_tag,
+    registry::{self, RegistryItem, RegistryValue},
+    sys, FontSubstitutions, Office, OfficeError,
+};
+
+/// Macro security levels ("Tools > Options > Security > Macro Security")
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(i64)]
+pub enum MacroSecurityLevel {
+    /// All macros are executed without confirmation
+    Low = 0,
+    /// Confirmation is required before executing macros from untrusted sources
+    Medium = 1,
+    /// Only signed macros from trusted sources are executed
+    High = 2,
+    /// Only macros from trusted locations are executed
+    VeryHigh = 3,
+}
+
+/// AutoCorrect options disabled by [ProfileConfig::autocorrect]
+const AUTOCORRECT_OPTIONS: &[&str] = &[
+    "ReplaceTable",
+    "TwoInitialCapitals",
+    "CapitalAtStartSentence",
+    "ChangeOrdinalNumber",
+    "ChangeDash",
+    "SetInetAttribute",
+];
+
+/// Configuration for a LibreOffice user profile, written to the
+/// "registrymodifications.xcu" of a new profile directory using
+/// [ProfileConfig::write] for use with [Office::new_with_profile]
+///
+/// Serializes into the contents of "registrymodifications.xcu" using
+/// [ToString::to_string]:
+///
+/// ```
+/// use libreofficekit::{MacroSecurityLevel, ProfileConfig};
+///
+/// let config = ProfileConfig::new()
+///     .macro_security_level(MacroSecurityLevel::VeryHigh)
+///     .update_check(false);
+///
+/// assert!(config.to_string().contains("MacroSecurityLevel"));
+/// ```
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct ProfileConfig {
+    /// Security level for executing macros
+    macro_security_level: Option<MacroSecurityLevel>,
+    /// Whether LibreOffice automatically checks for updates
+    update_check: Option<bool>,
+    /// Whether text is automatically corrected while typing
+    autocorrect: Option<bool>,
+    /// Whether a Java runtime can be used
+    java: Option<bool>,
+    /// Locale used for formatting (i.e "en-US")
+    locale: Option<String>,
+    /// Language of the user interface and messages (i.e "en-US")
+    ui_language: Option<String>,
+    /// Font replacement table
+    font_substitutions: Option<FontSubstitutions>,
+}
+
+impl ProfileConfig {
+    /// Creates a new empty profile configuration, which uses the LibreOffice defaults
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a profile configuration for processing untrusted documents, macros
+    /// are restricted to trusted locations and update checks, AutoCorrect and
+    /// Java are disabled
+    pub fn hardened() -> Self {
+        Self::new()
+            .macro_security_level(MacroSecurityLevel::VeryHigh)
+            .update_check(false)
+            .autocorrect(false)
+            .java(false)
+    }
+
+    /// Sets the security level for executing macros
+    pub fn macro_security_level(mut self, level: MacroSecurityLevel) -> Self {
+        self.macro_security_level = Some(level);
+        self
+    }
+
+    /// Sets whether LibreOffice automatically checks for updates
+    pub fn update_check(mut self, update_check: bool) -> Self {
+        self.update_check = Some(update_check);
+        self
+    }
+
+    /// Sets whether text is automatically corrected while typing (Replacements,
+    /// capitalization, ordinals, dashes and URL recognition)
+    pub fn autocorrect(mut self, autocorrect: bool) -> Self {
+        self.autocorrect = Some(autocorrect);
+        self
+    }
+
+    /// Sets whether a Java runtime can be used, some filters and database
+    /// features require Java
+    pub fn java(mut self, java: bool) -> Self {
+        self.java = Some(java);
+        self
+    }
+
+    /// Sets the locale used for formatting and the default document
+    /// language (i.e "en-US")
+    pub fn locale<S: Into<String>>(mut self, locale: S) -> Self {
+        self.locale = Some(locale.into());
+        self
+    }
+
+    /// Sets the language of the user interface and messages (i.e "en-US")
+    pub fn ui_language<S: Into<String>>(mut self, ui_language: S) -> Self {
+        self.ui_language = Some(ui_language.into());
+        self
+    }
+
+    /// Sets the font replacement table
+    pub fn font_substitutions(mut self, font_substitutions: FontSubstitutions) -> Self {
+        self.font_substitutions = Some(font_substitutions);
+        self
+    }
+
+    /// Configuration items for the profile
+    fn registry_items(&self) -> Vec<RegistryItem> {
+        let mut items = Vec::new();
+
+        if let Some(level) = self.macro_security_level {
+            items.push(RegistryItem::prop(
+                "/org.openoffice.Office.Common/Security/Scripting",
+                "MacroSecurityLevel",
+                RegistryValue::Int(level as i64),
+            ));
+        }
+
+        if let Some(update_check) = self.update_check {
+            items.push(RegistryItem::prop(
+                "/org.openoffice.Office.Jobs/Jobs/org.openoffice.Office.Jobs:Job['UpdateCheck']/Arguments",
+                "AutoCheckEnabled",
+                RegistryValue::Bool(update_check),
+            ));
+        }
+
+        if let Some(autocorrect) = self.autocorrect {
+            items.extend(AUTOCORRECT_OPTIONS.iter().map(|name| {
+                RegistryItem::prop(
+                    "/org.openoffice.Office.Common/AutoCorrect",
+                    name,
+                    RegistryValue::Bool(autocorrect),
+                )
+            }));
+        }
+
+        if let Some(java) = self.java {
+            items.push(RegistryItem::prop(
+                "/org.openoffice.Office.Java/VirtualMachine",
+                "Enable",
+                RegistryValue::Bool(java),
+            ));
+        }
+
+        if let Some(locale) = &self.locale {
+            items.push(RegistryItem::prop(
+                "/org.openoffice.Setup/L10N",
+                "ooSetupSystemLocale",
+                RegistryValue::String(locale.clone()),
+            ));
+            items.push(RegistryItem::prop(
+                "/org.openoffice.Office.Linguistic/General",
+                "DefaultLocale",
+                RegistryValue::String(locale.clone()),
+            ));
+        }
+
+        if let Some(ui_language) = &self.ui_language {
+            items.push(RegistryItem::prop(
+                "/org.openoffice.Setup/L10N",
+                "ooLocale",
+                RegistryValue::String(ui_language.clone()),
+            ));
+        }
+
+        if let Some(font_substitutions) = &self.font_substitutions {
+            items.extend(font_substitutions.registry_items());
+        }
+
+        items
+    }
+
+    /// Writes the profile configuration into a new profile directory at `path`,
+    /// the directory is created when it does not exist
+    ///
+    /// Returns [OfficeError::InvalidOptionValue] for invalid language tags and
+    /// an [std::io::ErrorKind::AlreadyExists] error when `path` already
+    /// contains a profile
+    pub fn write(&self, path: &Path) -> Result<(), OfficeError> {
+        for (option, value) in [("locale", &self.locale), ("ui_language", &self.ui_language)] {
+            if let Some(value) = value.as_ref().filter(|value| !is_language_tag(value)) {
+                return Err(OfficeError::InvalidOptionValue {
+                    option,
+                    value: value.clone(),
+                });
+            }
+        }
+
+        // Existing profiles contain configuration that would be replaced
+        if path.join("user").exists() {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::AlreadyExists,
+                "directory already contains a profile",
+            )
+            .into());
+        }
+
+        registry::write_registry_modifications(path, &self.registry_items())?;
+
+        Ok(())
+    }
+}
+
+impl fmt::Display for ProfileConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&registry::registry_modifications(&self.registry_items()))
+    }
+}
+
+impl Office {
+    /// Creates a new LOK instance from the provided install path using the
+    /// user profile directory at `profile_path` instead of the default profile
+    ///
+    /// Profiles can be prepared using [ProfileConfig::write], LibreOffice
+    /// creates a new profile when the directory is empty
+    pub fn new_with_profile<P: Into<PathBuf>>(
+        install_path: P,
+        profile_path: &Path,
+    ) -> Result<Office, OfficeError> {
+        let profile_path = std::path::absolute(profile_path)?;
+        let profile = sys::UserProfile::path(&profile_path)?;
+
+        Office::init(install_path.into(), Some(profile))
+    }
+
+    /// Creates a new LOK instance from the provided install path using a new
+    /// temporary user profile configured with `config`
+    ///
+    /// The profile is removed once the instance is dropped
+    pub fn new_with_profile_config<P: Into<PathBuf>>(
+        install_path: P,
+        config: &ProfileConfig,
+    ) -> Result<Office, OfficeError> {
+        let temp_dir = tempfile::Builder::new()
+            .prefix("libreofficekit-profile-")
+            .tempdir()?;
+
+        config.write(temp_dir.path())?;
+
+        let profile = sys::UserProfile::temporary(temp_dir)?;
+        Office::init(install_path.into(), Some(profile))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{MacroSecurityLevel, ProfileConfig, AUTOCORRECT_OPTIONS};
+    use crate::{FontSubstitutions, OfficeError};
+
+    /// Tests an empty profile contains no configuration
+    #[test]
+    fn test_empty() {
+        let value = ProfileConfig::new().to_string();
+        assert_eq!(value.lines().count(), 3);
+        assert!(value.ends_with("</oor:items>\n"));
+    }
+
+    /// Tests the profile configuration items
+    #[test]
+    fn test_profile_config() {
+        let value = ProfileConfig::hardened()
+            .macro_security_level(MacroSecurityLevel::High)
+            .locale("de-DE")
+            .ui_language("en-US")
+            .font_substitutions(FontSubstitutions::new().replace("Calibri", "Carlito"))
+            .to_string();
+
+        assert!(value.contains(concat!(
+            r#"<item oor:path="/org.openoffice.Office.Common/Security/Scripting">"#,
+            r#"<prop oor:name="MacroSecurityLevel" oor:op="fuse"><value>2</value></prop></item>"#
+        )));
+        assert!(value.contains(concat!(
+            r#"<item oor:path="/org.openoffice.Office.Jobs/Jobs/org.openoffice.Office.Jobs:Job[&apos;UpdateCheck&apos;]/Arguments">"#,
+            r#"<prop oor:name="AutoCheckEnabled" oor:op="fuse"><value>false</value></prop></item>"#
+        )));
+        assert!(value.contains(
+            r#"<prop oor:name="CapitalAtStartSentence" oor:op="fuse"><value>false</value></prop>"#
+        ));
+        assert!(value.contains(concat!(
+            r#"<item oor:path="/org.openoffice.Office.Java/VirtualMachine">"#,
+            r#"<prop oor:name="Enable" oor:op="fuse"><value>false</value></prop></item>"#
+        )));
+        assert!(value.contains(
+            r#"<prop oor:name="ooSetupSystemLocale" oor:op="fuse"><value>de-DE</value></prop>"#
+        ));
+        assert!(value
+            .contains(r#"<prop oor:name="ooLocale" oor:op="fuse"><value>en-US</value></prop>"#));
+        assert!(value.contains(
+            r#"<prop oor:name="SubstituteFont" oor:op="fuse"><value>Carlito</value></prop>"#
+        ));
+    }
+
+    /// Tests the hardened profile restricts macros and disables every
+    /// AutoCorrect option, update checks and Java
+    #[test]
+    fn test_hardened() {
+        let value = ProfileConfig::hardened().to_string();
+
+        assert!(value.contains(
+            r#"<prop oor:name="MacroSecurityLevel" oor:op="fuse"><value>3</value></prop>"#
+        ));
+
+        for option in AUTOCORRECT_OPTIONS {
+            assert!(value.contains(&format!(
+                r#"<prop oor:name="{option}" oor:op="fuse"><value>false</value></prop>"#
+            )));
+        }
+
+        // Later settings replace the hardened defaults
+        let value = ProfileConfig::hardened().java(true).to_string();
+        assert!(value.contains(concat!(
+            r#"<item oor:path="/org.openoffice.Office.Java/VirtualMachine">"#,
+            r#"<prop oor:name="Enable" oor:op="fuse"><value>true</value></prop></item>"#
+        )));
+        assert!(
+            !value.contains(r#"<prop oor:name="Enable" oor:op="fuse"><value>false</value></prop>"#)
+        );
+    }
+
+    /// Tests writing the profile into a new directory
+    #[test]
+    fn test_write() {
+        let directory = tempfile::tempdir().unwrap();
+        let path = directory.path().join("profile");
+
+        let config = ProfileConfig::hardened();
+        config.write(&path).unwrap();
+
+        let value = std::fs::read_to_string(path.join("user/registrymodifications.xcu")).unwrap();
+        assert_eq!(value, config.to_string());
+
+        // Existing profiles are not replaced
+        assert!(matches!(
+            config.write(&path),
+            Err(OfficeError::Io(err)) if err.kind() == std::io::ErrorKind::AlreadyExists
+        ));
+
+        assert!(matches!(
+            ProfileConfig::new()
+                .locale("en_US")
+                .write(&directory.path().join("other")),
+            Err(OfficeError::InvalidOptionValue {
+                option: "locale",
+                ..
+            })
+        ));
+        assert!(matches!(
+            ProfileConfig::new()
+                .ui_language("en-US\"/>")
+                .write(&directory.path().join("other")),
+            Err(OfficeError::InvalidOptionValue {
+                option: "ui_language",
+                ..
+            })
+        ));
+
+        // Invalid configurations do not create a profile
+        assert!(!directory.path().join("other").exists());
+    }
+}
@@ -0,0 +1,177 @@
+//! Writing of LibreOffice configuration changes in the user profile
+//! "registrymodifications.xcu" format
+
+use std::{fmt::Write, path::Path};
+
+/// Location of the registry modifications within a user profile directory
+const REGISTRY_MODIFICATIONS_PATH: &str = "user/registrymodifications.xcu";
+
+/// Value of a configuration property
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum RegistryValue {
+    Bool(bool),
+    Int(i64),
+    String(String),
+}
+
+/// Single configuration change
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum RegistryItem {
+    /// Sets the property `name` of the group at `path`
+    Prop {
+        path: String,
+        name: String,
+        value: RegistryValue,
+    },
+    /// Replaces the set entry `name` within the set at `path`
+    Node {
+        path: String,
+        name: String,
+        props: Vec<(String, RegistryValue)>,
+    },
+}
+
+impl RegistryItem {
+    /// Creates a property item
+    pub(crate) fn prop(path: &str, name: &str, value: RegistryValue) -> Self {
+        RegistryItem::Prop {
+            path: path.to_string(),
+            name: name.to_string(),
+            value,
+        }
+    }
+}
+
+/// Escapes `value` for use in XML text and attributes
+fn escape_xml(value: &str) -> String {
+    let mut output = String::with_capacity(value.len());
+
+    for c in value.chars() {
+        match c {
+            '&' => output.push_str("&amp;"),
+            '<' => output.push_str("&lt;"),
+            '>' => output.push_str("&gt;"),
+            '"' => output.push_str("&quot;"),
+            '\'' => output.push_str("&apos;"),
+            c => output.push(c),
+        }
+    }
+
+    output
+}
+
+fn write_prop(output: &mut String, name: &str, value: &RegistryValue) {
+    let value = match value {
+        RegistryValue::Bool(value) => value.to_string(),
+        RegistryValue::Int(value) => value.to_string(),
+        RegistryValue::String(value) => escape_xml(value),
+    };
+
+    _ = write!(
+        output,
+        r#"<prop oor:name="{}" oor:op="fuse"><value>{}</value></prop>"#,
+        escape_xml(name),
+        value
+    );
+}
+
+/// Creates the contents of a "registrymodifications.xcu" file for `items`
+pub(crate) fn registry_modifications(items: &[RegistryItem]) -> String {
+    let mut output = String::from(concat!(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n",
+        "<oor:items xmlns:oor=\"http://openoffice.org/2001/registry\" ",
+        "xmlns:xs=\"http://www.w3.org/2001/XMLSchema\" ",
+        "xmlns:xsi=\"http://www.w3.org/2001/XMLSchema-instance\">\n"
+    ));
+
+    for item in items {
+        match item {
+            RegistryItem::Prop { path, name, value } => {
+                _ = write!(output, r#"<item oor:path="{}">"#, escape_xml(path));
+                write_prop(&mut output, name, value);
+            }
+            RegistryItem::Node { path, name, props } => {
+                _ = write!(
+                    output,
+                    r#"<item oor:path="{}"><node oor:name="{}" oor:op="replace">"#,
+                    escape_xml(path),
+                    escape_xml(name)
+                );
+
+                for (name, value) in props {
+                    write_prop(&mut output, name, value);
+                }
+
+                output.push_str("</node>");
+            }
+        }
+
+        output.push_str("</item>\n");
+    }
+
+    output.push_str("</oor:items>\n");
+    output
+}
+
+/// Writes the "registrymodifications.xcu" for `items` into the user
+/// profile directory at `profile`
+pub(crate) fn write_registry_modifications(
+    profile: &Path,
+    items: &[RegistryItem],
+) -> std::io::Result<()> {
+    let path = profile.join(REGISTRY_MODIFICATIONS_PATH);
+
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+
+    std::fs::write(path, registry_modifications(items))
+}
+
+#[cfg(test)]
+mod test {
+    use super::{registry_modifications, RegistryItem, RegistryValue};
+
+    /// Tests writing registry modifications
+    #[test]
+    fn test_registry_modifications() {
+        let items = [
+            RegistryItem::prop(
+                "/org.openoffice.Office.Common/Font/Substitution",
+                "Replacement",
+                RegistryValue::Bool(true),
+            ),
+            RegistryItem::Node {
+                path: "/org.openoffice.Office.Common/Font/Substitution/FontPairs".to_string(),
+                name: "_0".to_string(),
+                props: vec![
+                    ("Always".to_string(), RegistryValue::Bool(true)),
+                    (
+                        "ReplaceFont".to_string(),
+                        RegistryValue::String("A & B".to_string()),
+                    ),
+                ],
+            },
+        ];
+
+        let value = registry_modifications(&items);
+        let lines: Vec<&str> = value.lines().collect();
+
+        assert_eq!(lines.len(), 5);
+        assert_eq!(
+            lines[2],
+            r#"<item oor:path="/org.openoffice.Office.Common/Font/Substitution"><prop oor:name="Replacement" oor:op="fuse"><value>true</value></prop></item>"#
+        );
+        assert_eq!(
+            lines[3],
+            concat!(
+                r#"<item oor:path="/org.openoffice.Office.Common/Font/Substitution/FontPairs">"#,
+                r#"<node oor:name="_0" oor:op="replace">"#,
+                r#"<prop oor:name="Always" oor:op="fuse"><value>true</value></prop>"#,
+                r#"<prop oor:name="ReplaceFont" oor:op="fuse"><value>A &amp; B</value></prop>"#,
+                "</node></item>"
+            )
+        );
+        assert_eq!(lines[4], "</oor:items>");
+    }
+}
@@ -0,0 +1,310 @@
+//! Capturing of the output LibreOffice writes to stderr during LOK calls
+
+#[cfg(target_os = "linux")]
+pub(crate) use linux::{capture, disable};
+
+/// Capturing is only supported on Linux, other platforms run `f` directly
+#[cfg(not(target_os = "linux"))]
+pub(crate) fn capture<T>(f: impl FnOnce() -> T) -> (T, Vec<String>) {
+    (f(), Vec::new())
+}
+
+#[cfg(target_os = "linux")]
+mod linux {
+    use std::{
+        fs::File,
+        io::{BufRead, BufReader, Write},
+        os::fd::{AsRawFd, FromRawFd, OwnedFd},
+        sync::mpsc::{self, Receiver, RecvTimeoutError},
+        time::Duration,
+    };
+
+    use parking_lot::Mutex;
+
+    use crate::{Office, OfficeError};
+
+    /// Active stderr capture, stderr is process wide so only one capture exists
+    static STDERR_CAPTURE: Mutex<Option<StderrCapture>> = Mutex::new(None);
+
+    /// Prefix of the marker line written after each call to find the end of its output
+    const END_MARKER: &str = "\u{1b}[libreofficekit-stderr-end:";
+
+    /// Maximum time to wait for the output of a call to be read
+    const READ_TIMEOUT: Duration = Duration::from_secs(5);
+
+    struct StderrCapture {
+        /// Write end of the pipe that replaces stderr during calls
+        write: File,
+        /// Duplicate of the original stderr
+        saved: OwnedFd,
+        /// Lines read from the pipe by the reader thread
+        receiver: Receiver<String>,
+        /// Captured lines that have not been taken
+        lines: Vec<String>,
+        /// Whether stderr is currently redirected
+        active: bool,
+        /// Number of the next end marker
+        next_marker: u64,
+    }
+
+    impl StderrCapture {
+        fn new() -> std::io::Result<Self> {
+            let mut fds = [0; 2];
+            if unsafe { libc::pipe2(fds.as_mut_ptr(), libc::O_CLOEXEC) } != 0 {
+                return Err(std::io::Error::last_os_error());
+            }
+
+            let (read, write) = unsafe { (File::from_raw_fd(fds[0]), File::from_raw_fd(fds[1])) };
+
+            let saved = unsafe { libc::fcntl(libc::STDERR_FILENO, libc::F_DUPFD_CLOEXEC, 0) };
+            if saved < 0 {
+                return Err(std::io::Error::last_os_error());
+            }
+            let saved = unsafe { OwnedFd::from_raw_fd(saved) };
+
+            // Read lines on a separate thread so a full pipe never blocks LibreOffice
+            let (sender, receiver) = mpsc::channel();
+            std::thread::Builder::new()
+                .name("libreofficekit-stderr".to_string())
+                .spawn(move || {
+                    let mut reader = BufReader::new(read);
+                    let mut line = Vec::new();
+
+                    // Ends once the write end is closed
+                    while reader
+                        .read_until(b'\n', &mut line)
+                        .is_ok_and(|length| length > 0)
+                    {
+                        let value = String::from_utf8_lossy(&line).trim_end().to_string();
+                        line.clear();
+
+                        if sender.send(value).is_err() {
+                            break;
+                        }
+                    }
+                })?;
+
+            Ok(Self {
+                write,
+                saved,
+                receiver,
+                lines: Vec::new(),
+                active: false,
+                next_marker: 0,
+            })
+        }
+
+        /// Redirects stderr into the pipe
+        fn begin(&mut self) {
+            if unsafe { libc::dup2(self.write.as_raw_fd(), libc::STDERR_FILENO) } >= 0 {
+                self.active = true;
+            }
+        }
+
+        /// Restores stderr and collects the lines written since [StderrCapture::begin],
+        /// provides the collected lines
+        fn end(&mut self) -> Vec<String> {
+            unsafe { libc::dup2(self.saved.as_raw_fd(), libc::STDERR_FILENO) };
+            self.active = false;
+
+            let marker = format!("{END_MARKER}{}]", self.next_marker);
+            self.next_marker += 1;
+
+            if writeln!(self.write, "{marker}").is_err() {
+                return Vec::new();
+            }
+
+            let mut lines = Vec::new();
+
+            // Everything written before the marker has been read once the marker is received
+            loop {
+                match self.receiver.recv_timeout(READ_TIMEOUT) {
+                    Ok(line) if line == marker => break,
+                    // Markers from previous calls that timed out
+                    Ok(line) if line.starts_with(END_MARKER) => {}
+                    Ok(line) => lines.push(line),
+                    Err(RecvTimeoutError::Timeout | RecvTimeoutError::Disconnected) => break,
+                }
+            }
+
+            self.lines.extend(lines.iter().cloned());
+            lines
+        }
+    }
+
+    /// Redirection started by [capture], stderr is restored when dropped so
+    /// it is not left redirected if the call panics
+    struct CaptureGuard {
+        /// Whether this guard started the redirection
+        started: bool,
+    }
+
+    impl CaptureGuard {
+        /// Restores stderr, provides the lines written since the redirection started
+        fn finish(mut self) -> Vec<String> {
+            self.end()
+        }
+
+        fn end(&mut self) -> Vec<String> {
+            if !std::mem::take(&mut self.started) {
+                return Vec::new();
+            }
+
+            match STDERR_CAPTURE.lock().as_mut() {
+                Some(capture) => capture.end(),
+                None => Vec::new(),
+            }
+        }
+    }
+
+    impl Drop for CaptureGuard {
+        fn drop(&mut self) {
+            self.end();
+        }
+    }
+
+    /// Runs `f` with stderr redirected into the capture when enabled, provides
+    /// the result of `f` along with the lines written to stderr while it ran
+    pub(crate) fn capture<T>(f: impl FnOnce() -> T) -> (T, Vec<String>) {
+        let started = match STDERR_CAPTURE.lock().as_mut() {
+            // Nested calls are captured by the outer call
+            Some(capture) if !capture.active => {
+                capture.begin();
+                capture.active
+            }
+            _ => false,
+        };
+
+        let guard = CaptureGuard { started };
+        let result = f();
+
+        (result, guard.finish())
+    }
+
+    /// Disables capturing and restores stderr
+    pub(crate) fn disable() {
+        // Dropping the capture closes the pipe which ends the reader thread
+        if let Some(mut capture) = STDERR_CAPTURE.lock().take() {
+            if capture.active {
+                capture.end();
+            }
+        }
+    }
+
+    impl Office {
+        /// Enables or disables capturing of the output LibreOffice writes to
+        /// stderr while loading and saving documents, captured lines can be
+        /// obtained using [Office::take_stderr]
+        ///
+        /// Lines captured while a load or save fails are also included in the
+        /// `stderr` field of the returned error (i.e [OfficeError::LoadFailed],
+        /// [OfficeError::SaveFailed] or [OfficeError::FileNotFound])
+        ///
+        /// ## Important
+        ///
+        /// Stderr is shared by the whole process, anything else written to
+        /// stderr while a document is loading or saving is also captured
+        pub fn set_stderr_capture(&self, enabled: bool) -> Result<(), OfficeError> {
+            if !enabled {
+                disable();
+                return Ok(());
+            }
+
+            let mut capture = STDERR_CAPTURE.lock();
+            if capture.is_none() {
+                *capture = Some(StderrCapture::new()?);
+            }
+
+            Ok(())
+        }
+
+        /// Takes the lines captured from stderr since the last call, see
+        /// [Office::set_stderr_capture]
+        pub fn take_stderr(&self) -> Vec<String> {
+            STDERR_CAPTURE
+                .lock()
+                .as_mut()
+                .map(|capture| std::mem::take(&mut capture.lines))
+                .unwrap_or_default()
+        }
+    }
+
+    #[cfg(test)]
+    mod test {
+        use std::{mem::MaybeUninit, os::fd::AsRawFd, panic, process::Command};
+
+        use super::{capture, disable, StderrCapture, STDERR_CAPTURE};
+
+        /// Environment variable marking the child process running the test
+        const CHILD_ENV: &str = "LIBREOFFICEKIT_STDERR_TEST_CHILD";
+
+        /// Inode of the file behind `fd`
+        fn inode(fd: i32) -> libc::ino_t {
+            let mut stat = MaybeUninit::<libc::stat>::uninit();
+            assert_eq!(unsafe { libc::fstat(fd, stat.as_mut_ptr()) }, 0);
+            unsafe { stat.assume_init() }.st_ino
+        }
+
+        /// Tests output written to stderr during a capture is collected and
+        /// stderr is restored when the captured call panics
+        ///
+        /// Stderr is shared by the whole process, so the test re-runs itself
+        /// alone in a child process rather than redirecting the stderr of
+        /// tests running in parallel
+        #[test]
+        fn test_capture_stderr() {
+            if std::env::var_os(CHILD_ENV).is_none() {
+                let output = Command::new(std::env::current_exe().unwrap())
+                    .args([
+                        "stderr::linux::test::test_capture_stderr",
+                        "--exact",
+                        "--test-threads=1",
+                    ])
+                    .env(CHILD_ENV, "1")
+                    .output()
+                    .unwrap();
+
+                let stdout = String::from_utf8_lossy(&output.stdout);
+                assert!(
+                    output.status.success() && stdout.contains("1 passed"),
+                    "{stdout}"
+                );
+                return;
+            }
+
+            *STDERR_CAPTURE.lock() = Some(StderrCapture::new().unwrap());
+
+            let ((), call_lines) = capture(|| {
+                let message = b"captured line\n";
+                unsafe { libc::write(libc::STDERR_FILENO, message.as_ptr().cast(), message.len()) };
+            });
+
+            let lines = STDERR_CAPTURE
+                .lock()
+                .as_mut()
+                .map(|capture| std::mem::take(&mut capture.lines))
+                .unwrap();
+
+            assert_eq!(lines, vec!["captured line".to_string()]);
+            assert_eq!(call_lines, lines);
+
+            let pipe = STDERR_CAPTURE
+                .lock()
+                .as_ref()
+                .map(|capture| inode(capture.write.as_raw_fd()))
+                .unwrap();
+
+            let result = panic::catch_unwind(|| capture(|| panic!("captured call panicked")));
+            assert!(result.is_err());
+
+            // Stderr no longer points at the pipe
+            assert!(STDERR_CAPTURE
+                .lock()
+                .as_ref()
+                .is_some_and(|capture| !capture.active));
+            assert_ne!(inode(libc::STDERR_FILENO), pipe);
+
+            disable();
+        }
+    }
+}
@@ -3,13 +3,14 @@ use std::{
     os::raw::{c_char, c_int, c_ulonglong, c_void},
     path::Path,
     ptr::null_mut,
-    sync::atomic::{AtomicBool, Ordering},
+    sync::atomic::{AtomicBool, AtomicU64, Ordering},
 };
 
 use crate::bindings::{LibreOfficeKit, LibreOfficeKitClass, LibreOfficeKitDocument};
 use dlopen2::wrapper::{Container, WrapperApi};
 use once_cell::sync::OnceCell;
 use parking_lot::Mutex;
+use tempfile::TempDir;
 
 use crate::{error::OfficeError, urls::DocUrl};
 
@@ -21,9 +22,25 @@ static LOK_CONTAINER: OnceCell<Container<LibreOfficeApi>> = OnceCell::new();
 /// a new one can be created
 pub(crate) static GLOBAL_OFFICE_LOCK: AtomicBool = AtomicBool::new(false);
 
+/// Whether LOK has been initialized within this process, LibreOffice cannot
+/// be initialized again after the instance is destroyed
+pub(crate) static LOK_INITIALIZED: AtomicBool = AtomicBool::new(false);
+
 /// Type used for the callback data
 pub type CallbackData = *mut Box<dyn FnMut(c_int, *const c_char)>;
 
+/// Shim wrapping a [CallbackData] callback function so it can be invoked by LOK
+unsafe extern "C" fn callback_shim(ty: c_int, payload: *const c_char, data: *mut c_void) {
+    // Get the callback function from the data argument
+    let callback: CallbackData = data.cast();
+
+    // Catch panics from calling the callback
+    _ = std::panic::catch_unwind(std::panic::AssertUnwindSafe(move || {
+        // Invoke the callback
+        (**callback)(ty, payload);
+    }));
+}
+
 #[cfg(target_os = "windows")]
 const TARGET_LIB: &str = "libsofficeapp.dll";
 #[cfg(target_os = "windows")]
@@ -60,6 +77,11 @@ struct LibreOfficeApi {
     >,
 }
 
+/// Checks if the LOK dynamic link library is present within the `install_path`
+pub(crate) fn has_library(install_path: &Path) -> bool {
+    install_path.join(TARGET_LIB).exists() || install_path.join(TARGET_MERGED_LIB).exists()
+}
+
 /// Loads the LOK functions from the dynamic link library
 fn lok_open(install_path: &Path) -> Result<Container<LibreOfficeApi>, OfficeError> {
     let target_lib_path = install_path.join(TARGET_LIB);
@@ -95,23 +117,67 @@ fn lok_open(install_path: &Path) -> Result<Container<LibreOfficeApi>, OfficeErro
     Err(OfficeError::MissingLibrary)
 }
 
-fn lok_init(install_path: &Path) -> Result<*mut LibreOfficeKit, OfficeError> {
+fn lok_init(
+    install_path: &Path,
+    user_profile_url: Option<&DocUrl>,
+) -> Result<*mut LibreOfficeKit, OfficeError> {
     // Try initialize the container (If not already initialized)
     let container = LOK_CONTAINER.get_or_try_init(|| lok_open(install_path))?;
 
-    // Get the hook function
-    let lok_hook = container
-        .libreofficekit_hook
-        .ok_or(OfficeError::MissingLibraryHook)?;
-
     let install_path = install_path.to_str().ok_or(OfficeError::InvalidPath)?;
     let install_path = CString::new(install_path)?;
 
-    let lok = lok_hook(install_path.as_ptr());
+    let lok = match user_profile_url {
+        Some(user_profile_url) => {
+            // Profiles can only be provided through the second hook
+            let lok_hook = container
+                .libreofficekit_hook_2
+                .ok_or(OfficeError::MissingLibraryHook)?;
+
+            let user_profile_url = CString::new(user_profile_url.to_string())?;
+            lok_hook(install_path.as_ptr(), user_profile_url.as_ptr())
+        }
+        None => {
+            // Get the hook function
+            let lok_hook = container
+                .libreofficekit_hook
+                .ok_or(OfficeError::MissingLibraryHook)?;
+
+            lok_hook(install_path.as_ptr())
+        }
+    };
 
     Ok(lok)
 }
 
+/// User profile provided to an instance
+pub struct UserProfile {
+    /// URL of the user profile directory
+    url: DocUrl,
+    /// Temporary profile directory, removed once the instance is destroyed
+    _temp_dir: Option<TempDir>,
+}
+
+impl UserProfile {
+    /// Uses the profile directory at `path`
+    pub fn path(path: &Path) -> Result<Self, OfficeError> {
+        let path = path.to_str().ok_or(OfficeError::InvalidPath)?;
+
+        Ok(Self {
+            url: DocUrl::from_absolute_path(path)?,
+            _temp_dir: None,
+        })
+    }
+
+    /// Uses the temporary profile directory `temp_dir`, the directory is
+    /// removed along with the profile
+    pub fn temporary(temp_dir: TempDir) -> Result<Self, OfficeError> {
+        let mut profile = Self::path(temp_dir.path())?;
+        profile._temp_dir = Some(temp_dir);
+        Ok(profile)
+    }
+}
+
 /// Raw office pointer access
 pub struct OfficeRaw {
     /// This pointer for LOK
@@ -120,23 +186,35 @@ pub struct OfficeRaw {
     class: *mut LibreOfficeKitClass,
     /// Callback data if specified
     callback_data: Mutex<CallbackData>,
+    /// Optional features bitset last set with [OfficeRaw::set_optional_features]
+    optional_features: AtomicU64,
+    /// User profile if specified
+    _profile: Option<UserProfile>,
 }
 
 impl OfficeRaw {
-    /// Initializes a new instance of LOK
-    pub unsafe fn init(install_path: &Path) -> Result<Self, OfficeError> {
-        let lok = lok_init(install_path)?;
+    /// Initializes a new instance of LOK, using the provided user `profile`
+    /// instead of the default profile when specified
+    pub unsafe fn init(
+        install_path: &Path,
+        profile: Option<UserProfile>,
+    ) -> Result<Self, OfficeError> {
+        let lok = lok_init(install_path, profile.as_ref().map(|profile| &profile.url))?;
 
         if lok.is_null() {
             return Err(OfficeError::UnknownInit);
         }
 
+        LOK_INITIALIZED.store(true, Ordering::SeqCst);
+
         let lok_class = (*lok).pClass;
 
         let instance = Self {
             this: lok,
             class: lok_class,
             callback_data: Mutex::new(null_mut()),
+            optional_features: AtomicU64::new(0),
+            _profile: profile,
         };
 
         Ok(instance)
@@ -151,7 +229,7 @@ impl OfficeRaw {
         let value = get_filter_types(self.this);
 
         if let Some(error) = self.get_error() {
-            return Err(OfficeError::OfficeError(error));
+            return Err(OfficeError::from_office_message(error, None));
         }
 
         Ok(CString::from_raw(value))
@@ -166,7 +244,7 @@ impl OfficeRaw {
         let value = get_version_info(self.this);
 
         if let Some(error) = self.get_error() {
-            return Err(OfficeError::OfficeError(error));
+            return Err(OfficeError::from_office_message(error, None));
         }
 
         Ok(CString::from_raw(value))
@@ -181,7 +259,7 @@ impl OfficeRaw {
         dump_state(self.this, std::ptr::null(), &mut state);
 
         if let Some(error) = self.get_error() {
-            return Err(OfficeError::OfficeError(error));
+            return Err(OfficeError::from_office_message(error, None));
         }
 
         Ok(CString::from_raw(state))
@@ -196,7 +274,7 @@ impl OfficeRaw {
 
         // Check for errors
         if let Some(error) = self.get_error() {
-            return Err(OfficeError::OfficeError(error));
+            return Err(OfficeError::from_office_message(error, None));
         }
 
         Ok(())
@@ -215,7 +293,7 @@ impl OfficeRaw {
 
         // Check for errors
         if let Some(error) = self.get_error() {
-            return Err(OfficeError::OfficeError(error));
+            return Err(OfficeError::from_office_message(error, None));
         }
 
         Ok(())
@@ -252,14 +330,7 @@ impl OfficeRaw {
             .ok_or(OfficeError::MissingFunction("documentLoad"))?;
         let this = document_load(self.this, url.as_ptr());
 
-        // Check for errors
-        if let Some(error) = self.get_error() {
-            return Err(OfficeError::OfficeError(error));
-        }
-
-        debug_assert!(!this.is_null());
-
-        Ok(DocumentRaw { this })
+        self.loaded_document(url, this)
     }
 
     /// Loads a document with additional options
@@ -273,14 +344,40 @@ impl OfficeRaw {
             .ok_or(OfficeError::MissingFunction("documentLoadWithOptions"))?;
         let this = document_load_with_options(self.this, url.as_ptr(), options);
 
+        self.loaded_document(url, this)
+    }
+
+    /// Checks the result of loading the document at `url`, creating the
+    /// [DocumentRaw] when a document was loaded
+    unsafe fn loaded_document(
+        &self,
+        url: &DocUrl,
+        this: *mut LibreOfficeKitDocument,
+    ) -> Result<DocumentRaw, OfficeError> {
         // Check for errors
         if let Some(error) = self.get_error() {
-            return Err(OfficeError::OfficeError(error));
+            return Err(OfficeError::from_office_message(error, Some(url)));
         }
 
-        debug_assert!(!this.is_null());
+        // Office may fail to load without reporting an error
+        if this.is_null() {
+            let reason = match url.to_file_path() {
+                Some(path) if !path.exists() => "file does not exist",
+                Some(path) if std::fs::File::open(&path).is_err() => "file is not readable",
+                _ => "office did not return a document",
+            };
+
+            return Err(OfficeError::LoadFailed {
+                url: url.clone(),
+                reason: reason.to_string(),
+                stderr: Vec::new(),
+            });
+        }
 
-        Ok(DocumentRaw { this })
+        Ok(DocumentRaw {
+            this,
+            callback_data: null_mut(),
+        })
     }
 
     /// Sets the current document password
@@ -300,7 +397,7 @@ impl OfficeRaw {
 
         // Check for errors
         if let Some(error) = self.get_error() {
-            return Err(OfficeError::OfficeError(error));
+            return Err(OfficeError::from_office_message(error, Some(url)));
         }
 
         Ok(())
@@ -315,12 +412,19 @@ impl OfficeRaw {
 
         // Check for errors
         if let Some(error) = self.get_error() {
-            return Err(OfficeError::OfficeError(error));
+            return Err(OfficeError::from_office_message(error, None));
         }
 
+        self.optional_features.store(features, Ordering::SeqCst);
+
         Ok(())
     }
 
+    /// Gets the optional features bitset last set with [OfficeRaw::set_optional_features]
+    pub fn optional_features(&self) -> u64 {
+        self.optional_features.load(Ordering::SeqCst)
+    }
+
     pub unsafe fn send_dialog_event(
         &self,
         window_id: c_ulonglong,
@@ -334,7 +438,7 @@ impl OfficeRaw {
 
         // Check for errors
         if let Some(error) = self.get_error() {
-            return Err(OfficeError::OfficeError(error));
+            return Err(OfficeError::from_office_message(error, None));
         }
 
         Ok(())
@@ -350,7 +454,7 @@ impl OfficeRaw {
         if result == 0 {
             // Check for errors
             if let Some(error) = self.get_error() {
-                return Err(OfficeError::OfficeError(error));
+                return Err(OfficeError::from_office_message(error, None));
             }
         }
 
@@ -367,7 +471,7 @@ impl OfficeRaw {
 
         // Check for errors
         if let Some(error) = self.get_error() {
-            return Err(OfficeError::OfficeError(error));
+            return Err(OfficeError::from_office_message(error, None));
         }
 
         self.free_callback();
@@ -379,18 +483,6 @@ impl OfficeRaw {
     where
         F: FnMut(c_int, *const c_char) + 'static,
     {
-        /// Create a shim to wrap the callback function so it can be invoked
-        unsafe extern "C" fn callback_shim(ty: c_int, payload: *const c_char, data: *mut c_void) {
-            // Get the callback function from the data argument
-            let callback: *mut Box<dyn FnMut(c_int, *const c_char)> = data.cast();
-
-            // Catch panics from calling the callback
-            _ = std::panic::catch_unwind(std::panic::AssertUnwindSafe(move || {
-                // Invoke the callback
-                (**callback)(ty, payload);
-            }));
-        }
-
         // Callback is double boxed then leaked
         let callback_ptr: *mut Box<dyn FnMut(c_int, *const c_char)> =
             Box::into_raw(Box::new(Box::new(callback)));
@@ -403,7 +495,7 @@ impl OfficeRaw {
 
         // Check for errors
         if let Some(error) = self.get_error() {
-            return Err(OfficeError::OfficeError(error));
+            return Err(OfficeError::from_office_message(error, None));
         }
 
         // Free any existing callbacks
@@ -477,8 +569,15 @@ impl OfficeRaw {
 
 impl Drop for OfficeRaw {
     fn drop(&mut self) {
+        #[cfg(feature = "tracing")]
+        let _span = tracing::info_span!("office_destroy").entered();
+
         unsafe { self.destroy() }
 
+        // Stop capturing stderr
+        #[cfg(target_os = "linux")]
+        crate::stderr::disable();
+
         // Unlock the global office lock
         GLOBAL_OFFICE_LOCK.store(false, Ordering::SeqCst)
     }
@@ -487,6 +586,8 @@ impl Drop for OfficeRaw {
 pub struct DocumentRaw {
     /// This pointer for the document
     this: *mut LibreOfficeKitDocument,
+    /// Callback data if specified
+    callback_data: CallbackData,
 }
 
 impl DocumentRaw {
@@ -505,6 +606,42 @@ impl DocumentRaw {
         Ok(save_as(self.this, url.as_ptr(), format, filter))
     }
 
+    /// Registers a callback for events emitted by the document
+    pub unsafe fn register_callback<F>(&mut self, callback: F) -> Result<(), OfficeError>
+    where
+        F: FnMut(c_int, *const c_char) + 'static,
+    {
+        let class = (*self.this).pClass;
+        let register_callback = (*class)
+            .registerCallback
+            .ok_or(OfficeError::MissingFunction("registerCallback"))?;
+
+        // Callback is double boxed then leaked
+        let callback_ptr: CallbackData = Box::into_raw(Box::new(Box::new(callback)));
+
+        register_callback(self.this, Some(callback_shim), callback_ptr.cast());
+
+        // Free any existing callbacks
+        self.free_callback();
+
+        // Store the new callback
+        self.callback_data = callback_ptr;
+
+        Ok(())
+    }
+
+    /// Frees the current allocated callback data memory if
+    /// a callback has been set
+    unsafe fn free_callback(&mut self) {
+        // Callback has not been set
+        if self.callback_data.is_null() {
+            return;
+        }
+
+        // Reclaim the raw memory
+        _ = Box::from_raw(std::mem::replace(&mut self.callback_data, null_mut()));
+    }
+
     /// Get the type of document
     pub unsafe fn get_document_type(&mut self) -> Result<i32, OfficeError> {
         let class = (*self.this).pClass;
@@ -519,6 +656,9 @@ impl DocumentRaw {
         let class = (*self.this).pClass;
         let destroy = (*class).destroy.expect("missing destroy function");
         destroy(self.this);
+
+        // Free the callback if allocated
+        self.free_callback();
     }
 }
 
@@ -0,0 +1,163 @@
+use std::{fmt, path::Path};
+
+use crate::TextEncoding;
+
+/// Options for the plain text filters ("Text" and "Text (encoded)")
+///
+/// Provide to [LoadOptions::filter_options] when loading a text document
+/// and to [Document::save_as] when saving as "txt" using [ToString::to_string]:
+///
+/// ```
+/// use libreofficekit::{LineEnding, TextEncoding, TextImportOptions};
+///
+/// let options = TextImportOptions::new()
+///     .encoding(TextEncoding::Windows1252)
+///     .line_ending(LineEnding::CrLf)
+///     .language("de-DE");
+///
+/// assert_eq!(options.to_string(), "MS_1252,CRLF,,de-DE,false");
+/// ```
+///
+/// [LoadOptions::filter_options]: crate::LoadOptions::filter_options
+/// [Document::save_as]: crate::Document::save_as
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TextImportOptions {
+    /// Character encoding of the text
+    encoding: TextEncoding,
+    /// Line ending marking a paragraph break
+    line_ending: LineEnding,
+    /// Default font used for imported text
+    font: Option<String>,
+    /// Language tag of the text (i.e en-US)
+    language: Option<String>,
+    /// Whether to write a byte-order mark when exporting
+    include_bom: bool,
+}
+
+/// Options for exporting plain text, the same options are used for both
+/// import and export
+pub type TextExportOptions = TextImportOptions;
+
+impl Default for TextImportOptions {
+    fn default() -> Self {
+        Self {
+            encoding: TextEncoding::Utf8,
+            line_ending: LineEnding::Lf,
+            font: None,
+            language: None,
+            include_bom: false,
+        }
+    }
+}
+
+impl TextImportOptions {
+    /// Creates the default text options (UTF-8 with LF line endings)
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates text options using the encoding detected from the file
+    /// at `path`, see [TextEncoding::detect]
+    pub fn detect(path: &Path) -> std::io::Result<Self> {
+        let encoding = TextEncoding::detect_file(path)?;
+        Ok(Self::default().encoding(encoding))
+    }
+
+    /// Sets the character encoding of the text
+    ///
+    /// [TextEncoding::Other] encodings are not supported by the text filters
+    /// and will leave the encoding up to LibreOffice
+    pub fn encoding(mut self, encoding: TextEncoding) -> Self {
+        self.encoding = encoding;
+        self
+    }
+
+    /// Sets the line ending, when importing each line ending starts a new
+    /// paragraph and when exporting paragraphs are separated by it
+    pub fn line_ending(mut self, line_ending: LineEnding) -> Self {
+        self.line_ending = line_ending;
+        self
+    }
+
+    /// Sets the default font used for imported text
+    pub fn font<S: Into<String>>(mut self, font: S) -> Self {
+        self.font = Some(font.into());
+        self
+    }
+
+    /// Sets the language tag of the text (i.e en-US)
+    pub fn language<S: Into<String>>(mut self, language: S) -> Self {
+        self.language = Some(language.into());
+        self
+    }
+
+    /// Sets whether a byte-order mark is written when exporting
+    pub fn include_bom(mut self, include_bom: bool) -> Self {
+        self.include_bom = include_bom;
+        self
+    }
+}
+
+impl fmt::Display for TextImportOptions {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{},{},{},{},{}",
+            self.encoding.name().unwrap_or_default(),
+            self.line_ending,
+            self.font.as_deref().unwrap_or_default(),
+            self.language.as_deref().unwrap_or_default(),
+            self.include_bom
+        )
+    }
+}
+
+/// Line endings for plain text
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum LineEnding {
+    /// Line feed (Unix)
+    #[default]
+    Lf,
+    /// Carriage return followed by line feed (Windows)
+    CrLf,
+    /// Carriage return (Classic Mac OS)
+    Cr,
+}
+
+impl fmt::Display for LineEnding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            LineEnding::Lf => "LF",
+            LineEnding::CrLf => "CRLF",
+            LineEnding::Cr => "CR",
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{LineEnding, TextImportOptions};
+    use crate::TextEncoding;
+
+    /// Tests the default options
+    #[test]
+    fn test_default() {
+        assert_eq!(TextImportOptions::new().to_string(), "UTF8,LF,,,false");
+    }
+
+    /// Tests all options serialize into their token positions
+    #[test]
+    fn test_all_options() {
+        let options = TextImportOptions::new()
+            .encoding(TextEncoding::Utf16)
+            .line_ending(LineEnding::Cr)
+            .font("Liberation Serif")
+            .language("fr-FR")
+            .include_bom(true);
+
+        assert_eq!(
+            options.to_string(),
+            "UNICODE,CR,Liberation Serif,fr-FR,true"
+        );
+    }
+}
@@ -0,0 +1,158 @@
+use std::path::Path;
+
+use parking_lot::Mutex;
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::{Office, OfficeError};
+
+/// Events of the active trace, only one office instance can exist so the
+/// trace is process wide
+static ACTIVE_TRACE: Mutex<Option<Vec<Value>>> = Mutex::new(None);
+
+/// Trace events recorded between [Office::start_trace] and [Office::stop_trace]
+/// in the Chrome trace event format, viewable in Perfetto (<https://ui.perfetto.dev>)
+/// or "chrome://tracing"
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct TraceFile {
+    #[serde(rename = "traceEvents")]
+    events: Vec<Value>,
+    #[serde(rename = "displayTimeUnit")]
+    display_time_unit: &'static str,
+}
+
+impl TraceFile {
+    fn new(events: Vec<Value>) -> Self {
+        Self {
+            events,
+            display_time_unit: "ms",
+        }
+    }
+
+    /// Recorded trace events
+    pub fn events(&self) -> &[Value] {
+        &self.events
+    }
+
+    /// Whether no events were recorded
+    pub fn is_empty(&self) -> bool {
+        self.events.is_empty()
+    }
+
+    /// Serializes the trace as Chrome trace JSON
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).expect("trace events are valid json")
+    }
+
+    /// Writes the trace as Chrome trace JSON to the file at `path`
+    pub fn write_to(&self, path: &Path) -> std::io::Result<()> {
+        std::fs::write(path, self.to_json())
+    }
+}
+
+/// Parses the events from a [CallbackType::ProfileFrame] payload, the payload
+/// contains one JSON event per line
+///
+/// [CallbackType::ProfileFrame]: crate::CallbackType::ProfileFrame
+fn parse_frame(payload: &str) -> impl Iterator<Item = Value> + '_ {
+    payload
+        .lines()
+        .map(|line| line.trim().trim_end_matches(','))
+        .filter(|line| !line.is_empty())
+        .filter_map(|line| serde_json::from_str::<Value>(line).ok())
+        .filter(Value::is_object)
+}
+
+/// Adds the events from a [CallbackType::ProfileFrame] payload to the active trace
+///
+/// [CallbackType::ProfileFrame]: crate::CallbackType::ProfileFrame
+pub(crate) fn record_frame(payload: &str) {
+    if let Some(events) = ACTIVE_TRACE.lock().as_mut() {
+        events.extend(parse_frame(payload));
+    }
+}
+
+/// Whether a trace is being recorded
+pub(crate) fn is_active() -> bool {
+    ACTIVE_TRACE.lock().is_some()
+}
+
+impl Office {
+    /// Starts recording a performance trace, events are collected until
+    /// [Office::stop_trace] is called. Starting while a trace is active
+    /// discards the events recorded so far
+    ///
+    /// Trace events are delivered through the office callback alongside any
+    /// callback registered with [Office::register_callback]
+    pub fn start_trace(&self) -> Result<(), OfficeError> {
+        self.callbacks.install(&self.raw)?;
+
+        *ACTIVE_TRACE.lock() = Some(Vec::new());
+
+        if let Err(err) = self.set_trace_event_recording(true) {
+            self.end_trace()?;
+            return Err(err);
+        }
+
+        Ok(())
+    }
+
+    /// Stops the trace started by [Office::start_trace] providing the recorded events
+    pub fn stop_trace(&self) -> Result<TraceFile, OfficeError> {
+        if ACTIVE_TRACE.lock().is_none() {
+            return Err(OfficeError::TraceNotStarted);
+        }
+
+        // Stopping flushes any pending events to the callback
+        let result = self.set_trace_event_recording(false);
+        let events = self.end_trace()?;
+        result?;
+
+        Ok(TraceFile::new(events))
+    }
+
+    /// Ends the active trace, provides the recorded events
+    fn end_trace(&self) -> Result<Vec<Value>, OfficeError> {
+        let events = ACTIVE_TRACE.lock().take().unwrap_or_default();
+
+        // The office callback is no longer needed when nothing else uses it
+        self.callbacks.release(&self.raw)?;
+
+        Ok(events)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use serde_json::json;
+
+    use super::{parse_frame, TraceFile};
+
+    /// Tests parsing the events from profile frame payloads
+    #[test]
+    fn test_parse_frame() {
+        let payload = concat!(
+            "{\"name\":\"load\",\"ph\":\"X\",\"ts\":10,\"dur\":5,\"pid\":1,\"tid\":1},\n",
+            "\n",
+            "{\"name\":\"save\",\"ph\":\"i\",\"ts\":20,\"pid\":1,\"tid\":1}\n",
+            "not json\n"
+        );
+
+        let events: Vec<_> = parse_frame(payload).collect();
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0]["name"], "load");
+        assert_eq!(events[1]["ph"], "i");
+    }
+
+    /// Tests serializing trace files as Chrome trace JSON
+    #[test]
+    fn test_trace_file_json() {
+        let trace = TraceFile::new(vec![json!({ "name": "load", "ph": "X" })]);
+
+        assert_eq!(
+            trace.to_json(),
+            r#"{"traceEvents":[{"name":"load","ph":"X"}],"displayTimeUnit":"ms"}"#
+        );
+        assert!(TraceFile::new(Vec::new()).is_empty());
+    }
+}
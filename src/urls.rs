@@ -1,6 +1,6 @@
 use std::ffi::{c_char, CString};
 use std::fmt;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use url::Url;
 
 use crate::error::OfficeError;
@@ -59,6 +59,19 @@ impl DocUrl {
 
         Ok(DocUrl(value_str))
     }
+
+    /// Converts the URL back into a local file path, [None] for remote
+    /// URIs and URLs that are not valid file paths
+    pub fn to_file_path(&self) -> Option<PathBuf> {
+        let value = self.0.to_str().ok()?;
+        let url = Url::parse(value).ok()?;
+
+        if url.scheme() != "file" {
+            return None;
+        }
+
+        url.to_file_path().ok()
+    }
 }
 
 impl fmt::Display for DocUrl {
@@ -109,6 +122,19 @@ mod test {
         let _url = DocUrl::from_remote_uri(path).unwrap();
     }
 
+    /// Tests converting URLs back into file paths
+    #[test]
+    fn test_to_file_path() {
+        let url = DocUrl::from_absolute_path("/tmp/file name.docx").unwrap();
+        assert_eq!(
+            url.to_file_path().as_deref(),
+            Some(std::path::Path::new("/tmp/file name.docx"))
+        );
+
+        let url = DocUrl::from_remote_uri("http://localhost:5555/file.docx").unwrap();
+        assert_eq!(url.to_file_path(), None);
+    }
+
     /// Tests an invalid remote paths
     #[test]
     fn test_invalid_remote_path() {
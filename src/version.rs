@@ -0,0 +1,288 @@
+use std::{fmt::Display, str::FromStr};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{InvalidProductVersion, OfficeVersionInfo, ProductVersion};
+
+/// Full LibreOffice version including the micro and build components
+/// (i.e 7.6.4.1), ordered by each component in turn
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Hash)]
+pub struct FullVersion {
+    pub major: u32,
+    pub minor: u32,
+    pub micro: u32,
+    pub build: u32,
+}
+
+impl FullVersion {
+    pub const fn new(major: u32, minor: u32, micro: u32, build: u32) -> Self {
+        Self {
+            major,
+            minor,
+            micro,
+            build,
+        }
+    }
+
+    /// Creates a full version from the `ProductVersion` and `ProductExtension`
+    /// reported by LibreOffice (i.e "7.6" and ".4.1"), any non numeric suffix
+    /// of the extension (i.e ".0.0.alpha0+") is ignored
+    pub fn from_product(version: ProductVersion, extension: &str) -> Self {
+        let mut components = extension
+            .trim_start_matches('.')
+            .split('.')
+            .map_while(leading_number);
+
+        Self {
+            major: version.major,
+            minor: version.minor,
+            micro: components.next().unwrap_or_default(),
+            build: components.next().unwrap_or_default(),
+        }
+    }
+
+    /// Major and minor components of the version
+    pub fn product_version(&self) -> ProductVersion {
+        ProductVersion::new(self.major, self.minor)
+    }
+
+    /// Known issues affecting this version, see [KNOWN_ISSUES]
+    pub fn known_issues(&self) -> impl Iterator<Item = &'static KnownIssue> + '_ {
+        KNOWN_ISSUES.iter().filter(|issue| issue.affects(self))
+    }
+}
+
+/// Parses the leading digits of a version component
+fn leading_number(value: &str) -> Option<u32> {
+    let end = value
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(value.len());
+
+    value[..end].parse().ok()
+}
+
+impl From<ProductVersion> for FullVersion {
+    fn from(value: ProductVersion) -> Self {
+        Self::new(value.major, value.minor, 0, 0)
+    }
+}
+
+impl Display for FullVersion {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}.{}.{}.{}",
+            self.major, self.minor, self.micro, self.build
+        )
+    }
+}
+
+impl FromStr for FullVersion {
+    type Err = InvalidProductVersion;
+
+    /// Parses a version with at least the major and minor components
+    /// (i.e "7.6", "7.6.4" or "7.6.4.1"), missing components are zero
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut components = s.trim().split('.').map(|value| value.parse::<u32>());
+        let mut next = || {
+            components
+                .next()
+                .transpose()
+                .map_err(|_| InvalidProductVersion)
+        };
+
+        let major = next()?.ok_or(InvalidProductVersion)?;
+        let minor = next()?.ok_or(InvalidProductVersion)?;
+        let micro = next()?.unwrap_or_default();
+        let build = next()?.unwrap_or_default();
+
+        Ok(Self::new(major, minor, micro, build))
+    }
+}
+
+impl<'de> Deserialize<'de> for FullVersion {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let value: &str = <&str>::deserialize(deserializer)?;
+
+        value
+            .parse::<FullVersion>()
+            .map_err(|err| serde::de::Error::custom(err.to_string()))
+    }
+}
+
+impl Serialize for FullVersion {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        self.to_string().serialize(serializer)
+    }
+}
+
+/// Parsed LibreOffice build identifier
+///
+/// Release builds report the git commit hash, development builds may also
+/// include the build time (i.e "TinderBox: Linux-x86_64@tb, Branch:master, Time: 2024-09-01_01:23:45")
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BuildId {
+    /// The unmodified build identifier
+    pub raw: String,
+    /// Git commit hash the build was created from
+    pub hash: Option<String>,
+    /// Date the build was created (YYYY-MM-DD)
+    pub date: Option<String>,
+}
+
+impl BuildId {
+    /// Parses the hash and date from a build identifier, unknown formats
+    /// are kept as only the raw value
+    pub fn parse(value: &str) -> Self {
+        let hash = value
+            .split(|c: char| !c.is_ascii_alphanumeric())
+            .find(|token| token.len() >= 7 && token.chars().all(|c| c.is_ascii_hexdigit()))
+            .map(str::to_string);
+
+        let date = value
+            .find("Time:")
+            .map(|index| value[index + "Time:".len()..].trim_start())
+            .and_then(|value| value.get(..10))
+            .filter(|date| is_date(date))
+            .map(str::to_string);
+
+        Self {
+            raw: value.to_string(),
+            hash,
+            date,
+        }
+    }
+}
+
+/// Checks if the value is a YYYY-MM-DD date
+fn is_date(value: &str) -> bool {
+    value.len() == 10
+        && value.char_indices().all(|(index, c)| match index {
+            4 | 7 => c == '-',
+            _ => c.is_ascii_digit(),
+        })
+}
+
+impl OfficeVersionInfo {
+    /// Full version of the install from the product version and extension
+    pub fn full_version(&self) -> FullVersion {
+        FullVersion::from_product(self.product_version, &self.product_extension)
+    }
+
+    /// Parsed build identifier of the install
+    pub fn build_id(&self) -> BuildId {
+        BuildId::parse(&self.build_id)
+    }
+}
+
+/// Known LibreOffice bug affecting a range of versions
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KnownIssue {
+    /// Short identifier for the issue
+    pub id: &'static str,
+    /// Description of the issue
+    pub description: &'static str,
+    /// First version affected by the issue
+    pub introduced: FullVersion,
+    /// First version where the issue is fixed, [None] when not yet fixed
+    pub fixed: Option<FullVersion>,
+}
+
+impl KnownIssue {
+    /// Checks if the issue affects the provided `version`
+    pub fn affects(&self, version: &FullVersion) -> bool {
+        let before_fix = match &self.fixed {
+            Some(fixed) => version < fixed,
+            None => true,
+        };
+
+        version >= &self.introduced && before_fix
+    }
+}
+
+/// Known LibreOffice bugs that affect usage through this crate
+pub const KNOWN_ISSUES: &[KnownIssue] = &[KnownIssue {
+    id: "exit-segfault",
+    description: "process end cleanup causes a segmentation fault when the program exits",
+    introduced: FullVersion::new(7, 0, 0, 0),
+    fixed: None,
+}];
+
+#[cfg(test)]
+mod test {
+    use super::{BuildId, FullVersion};
+    use crate::ProductVersion;
+
+    /// Tests parsing and ordering full versions
+    #[test]
+    fn test_full_version() {
+        let version: FullVersion = "7.6.4.1".parse().unwrap();
+        assert_eq!(version, FullVersion::new(7, 6, 4, 1));
+        assert_eq!(version.to_string(), "7.6.4.1");
+
+        assert_eq!(
+            "7.6".parse::<FullVersion>().unwrap(),
+            FullVersion::new(7, 6, 0, 0)
+        );
+        assert!("7".parse::<FullVersion>().is_err());
+        assert!("7.x.1".parse::<FullVersion>().is_err());
+
+        assert!(FullVersion::new(7, 6, 4, 1) < FullVersion::new(7, 6, 7, 2));
+        assert!(FullVersion::new(6, 4, 7, 2) < FullVersion::new(24, 2, 0, 0));
+    }
+
+    /// Tests creating full versions from the product version and extension
+    #[test]
+    fn test_full_version_from_product() {
+        let product = ProductVersion::new(7, 6);
+        assert_eq!(
+            FullVersion::from_product(product, ".4.1"),
+            FullVersion::new(7, 6, 4, 1)
+        );
+        assert_eq!(
+            FullVersion::from_product(ProductVersion::new(25, 2), ".0.0.alpha0+"),
+            FullVersion::new(25, 2, 0, 0)
+        );
+        assert_eq!(
+            FullVersion::from_product(product, ""),
+            FullVersion::new(7, 6, 0, 0)
+        );
+    }
+
+    /// Tests parsing build identifiers
+    #[test]
+    fn test_build_id() {
+        let build_id = BuildId::parse("e19e193f88cd6c0525a17fb7a176ed8e6a3e2aa1");
+        assert_eq!(
+            build_id.hash.as_deref(),
+            Some("e19e193f88cd6c0525a17fb7a176ed8e6a3e2aa1")
+        );
+        assert_eq!(build_id.date, None);
+
+        let build_id = BuildId::parse(
+            "TinderBox: Linux-x86_64@tb, Branch:master, Time: 2024-09-01_01:23:45, Hash: 4c3cd9a5b2",
+        );
+        assert_eq!(build_id.hash.as_deref(), Some("4c3cd9a5b2"));
+        assert_eq!(build_id.date.as_deref(), Some("2024-09-01"));
+    }
+
+    /// Tests known issues are matched against versions
+    #[test]
+    fn test_known_issues() {
+        let issues = |version: FullVersion| {
+            version
+                .known_issues()
+                .map(|issue| issue.id)
+                .collect::<Vec<_>>()
+        };
+
+        assert!(issues(FullVersion::new(6, 4, 7, 2)).is_empty());
+        assert_eq!(issues(FullVersion::new(7, 6, 4, 1)), vec!["exit-segfault"]);
+    }
+}
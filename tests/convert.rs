@@ -1,13 +1,24 @@
 use std::{
+    path::Path,
     rc::Rc,
-    sync::atomic::{AtomicBool, Ordering},
+    sync::atomic::{AtomicBool, AtomicUsize, Ordering},
 };
 
-use libreofficekit::{CallbackType, DocUrl, DocumentType, Office, OfficeOptionalFeatures};
+use libreofficekit::{
+    CallbackType, ConversionReport, ConvertJob, ConvertOptions, CsvFilterOptions, DocUrl,
+    DocumentType, FormatHint, LoadOptions, Office, OfficeError, OfficeOptionalFeatures, PdfALevel,
+    PdfExportOptions, SharedOffice, TextEncoding, TextExportOptions, TextImportOptions,
+};
+
+/// Obtains the shared office instance
+fn shared_office() -> SharedOffice {
+    // SAFETY: Documents and instances created by each test are dropped before the guard
+    unsafe { Office::shared() }.expect("failed to create office instance")
+}
 
 #[test]
 fn test_sample_docx() {
-    let office = Office::new(Office::find_install_path().unwrap()).unwrap();
+    let office = shared_office();
 
     let input_url = DocUrl::from_relative_path("./tests/samples/sample-docx.docx").unwrap();
     let output_url = DocUrl::from_absolute_path("/tmp/test.pdf").unwrap();
@@ -18,12 +29,12 @@ fn test_sample_docx() {
 
     assert_eq!(document_type, DocumentType::Text);
 
-    let _doc = document.save_as(&output_url, "pdf", None).unwrap();
+    document.save_as(&output_url, "pdf", None).unwrap();
 }
 
 #[test]
 fn test_sample_docx_encrypted() {
-    let office = Office::new(Office::find_install_path().unwrap()).unwrap();
+    let office = shared_office();
 
     let input_url =
         DocUrl::from_relative_path("./tests/samples/sample-docx-encrypted.docx").unwrap();
@@ -62,7 +73,7 @@ fn test_sample_docx_encrypted() {
 
 #[test]
 fn test_sample_docx_encrypted_known_password() {
-    let office = Office::new(Office::find_install_path().unwrap()).unwrap();
+    let office = shared_office();
 
     let input_url =
         DocUrl::from_relative_path("./tests/samples/sample-docx-encrypted.docx").unwrap();
@@ -107,7 +118,7 @@ fn test_sample_docx_encrypted_known_password() {
 
 #[test]
 fn test_sample_xlsx() {
-    let office = Office::new(Office::find_install_path().unwrap()).unwrap();
+    let office = shared_office();
 
     let input_url = DocUrl::from_relative_path("./tests/samples/sample-xlsx.xlsx").unwrap();
     let output_url = DocUrl::from_absolute_path("/tmp/test.pdf").unwrap();
@@ -118,12 +129,12 @@ fn test_sample_xlsx() {
 
     assert_eq!(document_type, DocumentType::Spreadsheet);
 
-    let _doc = document.save_as(&output_url, "pdf", None).unwrap();
+    document.save_as(&output_url, "pdf", None).unwrap();
 }
 
 #[test]
 fn test_sample_txt() {
-    let office = Office::new(Office::find_install_path().unwrap()).unwrap();
+    let office = shared_office();
 
     let input_url = DocUrl::from_relative_path("./tests/samples/sample-text.txt").unwrap();
     let output_url = DocUrl::from_absolute_path("/tmp/test.pdf").unwrap();
@@ -134,5 +145,465 @@ fn test_sample_txt() {
 
     assert_eq!(document_type, DocumentType::Text);
 
-    let _doc = document.save_as(&output_url, "pdf", None).unwrap();
+    document.save_as(&output_url, "pdf", None).unwrap();
+}
+
+#[test]
+fn test_sample_docx_modify_password_known() {
+    let office = shared_office();
+
+    let input_url =
+        DocUrl::from_relative_path("./tests/samples/sample-docx-modify-password.docx").unwrap();
+
+    let result = office
+        .document_load_with_modify_password(&input_url, Some("password"))
+        .unwrap();
+
+    // Correct password allows editing
+    assert!(result.editable);
+}
+
+#[test]
+fn test_sample_docx_modify_password_unknown() {
+    let office = shared_office();
+
+    let input_url =
+        DocUrl::from_relative_path("./tests/samples/sample-docx-modify-password.docx").unwrap();
+
+    let result = office
+        .document_load_with_modify_password(&input_url, None)
+        .unwrap();
+
+    // Document is opened read-only
+    assert!(!result.editable);
+}
+
+#[test]
+fn test_sample_docx_modify_password_incorrect() {
+    let office = shared_office();
+
+    let input_url =
+        DocUrl::from_relative_path("./tests/samples/sample-docx-modify-password.docx").unwrap();
+
+    let result = office
+        .document_load_with_modify_password(&input_url, Some("incorrect"))
+        .unwrap();
+
+    // Incorrect password falls back to read-only
+    assert!(!result.editable);
+}
+
+#[test]
+fn test_sample_docx_load_options() {
+    let office = shared_office();
+
+    let input_url = DocUrl::from_relative_path("./tests/samples/sample-docx.docx").unwrap();
+    let options = LoadOptions::new()
+        .language("en-US")
+        .batch(true)
+        .enable_macros(false);
+
+    let mut document = office
+        .document_load_with_options(&input_url, &options)
+        .unwrap();
+
+    let document_type = document.get_document_type().unwrap();
+
+    assert_eq!(document_type, DocumentType::Text);
+}
+
+#[test]
+fn test_convert_docx_pdf() {
+    let office = shared_office();
+
+    let output = office
+        .convert(
+            Path::new("./tests/samples/sample-docx.docx"),
+            Path::new("/tmp/test-convert-docx.pdf"),
+            ConvertOptions::new(),
+        )
+        .unwrap();
+
+    assert_eq!(output.document_type, DocumentType::Text);
+    assert!(output.size > 0);
+}
+
+#[test]
+fn test_convert_xlsx_pdf() {
+    let office = shared_office();
+
+    let output = office
+        .convert(
+            Path::new("./tests/samples/sample-xlsx.xlsx"),
+            Path::new("/tmp/test-convert-xlsx.pdf"),
+            ConvertOptions::new(),
+        )
+        .unwrap();
+
+    assert_eq!(output.document_type, DocumentType::Spreadsheet);
+    assert!(output.size > 0);
+}
+
+#[test]
+fn test_convert_unsupported_format() {
+    let office = shared_office();
+
+    let err = office
+        .convert(
+            Path::new("./tests/samples/sample-docx.docx"),
+            Path::new("/tmp/test-convert-docx.xlsx"),
+            ConvertOptions::new(),
+        )
+        .unwrap_err();
+
+    assert!(matches!(err, OfficeError::UnsupportedExportFormat { .. }));
+}
+
+#[test]
+fn test_sample_docx_pdf_options() {
+    let office = shared_office();
+
+    let input_url = DocUrl::from_relative_path("./tests/samples/sample-docx.docx").unwrap();
+    let output_url = DocUrl::from_absolute_path("/tmp/test-pdfa.pdf").unwrap();
+
+    let mut document = office.document_load(&input_url).unwrap();
+
+    let options = PdfExportOptions::new()
+        .pdfa(PdfALevel::PdfA2b)
+        .page_range("1")
+        .bookmarks(true)
+        .watermark("Draft");
+
+    document
+        .save_as(&output_url, "pdf", Some(&options.to_string()))
+        .unwrap();
+}
+
+#[test]
+fn test_sample_xlsx_csv_options() {
+    let office = shared_office();
+
+    let input_url = DocUrl::from_relative_path("./tests/samples/sample-xlsx.xlsx").unwrap();
+    let output_url = DocUrl::from_absolute_path("/tmp/test-csv-options.csv").unwrap();
+
+    let mut document = office.document_load(&input_url).unwrap();
+
+    let options = CsvFilterOptions::new()
+        .field_separator(';')
+        .quote_all_text(true);
+
+    document
+        .save_as(&output_url, "csv", Some(&options.to_string()))
+        .unwrap();
+}
+
+#[test]
+fn test_convert_xlsx_csv_sheets() {
+    let office = shared_office();
+
+    let files = office
+        .convert_csv_sheets(
+            Path::new("./tests/samples/sample-xlsx.xlsx"),
+            Path::new("/tmp/test-csv-sheets.csv"),
+            CsvFilterOptions::new(),
+        )
+        .unwrap();
+
+    assert!(!files.is_empty());
+}
+
+#[test]
+fn test_sample_txt_legacy_encoding() {
+    let office = shared_office();
+
+    let input_path = Path::new("./tests/samples/sample-text-windows-1252.txt");
+    let input_url = DocUrl::from_relative_path(input_path.to_str().unwrap()).unwrap();
+    let output_url = DocUrl::from_absolute_path("/tmp/test-text-utf8.txt").unwrap();
+
+    // Encoding is detected from the file content
+    let options = TextImportOptions::detect(input_path).unwrap();
+    assert_eq!(
+        options,
+        TextImportOptions::new().encoding(TextEncoding::Windows1252)
+    );
+
+    let load_options = LoadOptions::new().filter_options(options.to_string());
+    let mut document = office
+        .document_load_with_options(&input_url, &load_options)
+        .unwrap();
+
+    let export_options = TextExportOptions::new().encoding(TextEncoding::Utf8);
+
+    document
+        .save_as(&output_url, "txt", Some(&export_options.to_string()))
+        .unwrap();
+
+    let output = std::fs::read_to_string("/tmp/test-text-utf8.txt").unwrap();
+    assert!(output.contains("Café"));
+}
+
+#[test]
+fn test_convert_bytes_docx_pdf() {
+    let office = shared_office();
+
+    let input = std::fs::read("./tests/samples/sample-docx.docx").unwrap();
+
+    let output = office
+        .convert_bytes(&input, FormatHint::Extension("docx".to_string()), "pdf")
+        .unwrap();
+
+    assert!(output.starts_with(b"%PDF"));
+}
+
+#[test]
+fn test_sample_docx_export_many() {
+    let office = shared_office();
+
+    let input_url = DocUrl::from_relative_path("./tests/samples/sample-docx.docx").unwrap();
+
+    let mut document = office.document_load(&input_url).unwrap();
+
+    let results = document.export_many(&[
+        (
+            DocUrl::from_absolute_path("/tmp/test-many.pdf").unwrap(),
+            "pdf",
+            None,
+        ),
+        // Invalid format for a text document
+        (
+            DocUrl::from_absolute_path("/tmp/test-many.xlsx").unwrap(),
+            "xlsx",
+            None,
+        ),
+        (
+            DocUrl::from_absolute_path("/tmp/test-many.png").unwrap(),
+            "png",
+            None,
+        ),
+        (
+            DocUrl::from_absolute_path("/tmp/test-many.txt").unwrap(),
+            "txt",
+            None,
+        ),
+    ]);
+
+    assert_eq!(results.len(), 4);
+    assert!(results[0].is_ok());
+    assert!(results[1].is_err());
+    // Failed export does not prevent the remaining exports
+    assert!(results[2].is_ok());
+    assert!(results[3].is_ok());
+}
+
+#[test]
+fn test_convert_batch() {
+    let office = shared_office();
+
+    let progress_events = Rc::new(AtomicUsize::new(0));
+
+    let jobs = vec![
+        ConvertJob::new(
+            "./tests/samples/sample-docx.docx",
+            "/tmp/test-batch-docx.pdf",
+        ),
+        ConvertJob::new(
+            "./tests/samples/missing.docx",
+            "/tmp/test-batch-missing.pdf",
+        ),
+        ConvertJob::new(
+            "./tests/samples/sample-xlsx.xlsx",
+            "/tmp/test-batch-xlsx.pdf",
+        ),
+    ];
+
+    let reports: Vec<ConversionReport> = office
+        .convert_batch(jobs, {
+            let progress_events = progress_events.clone();
+            move |_, _| {
+                progress_events.fetch_add(1, Ordering::SeqCst);
+            }
+        })
+        .unwrap()
+        .collect();
+
+    assert_eq!(reports.len(), 3);
+    assert!(reports[0].output_size().is_some_and(|size| size > 0));
+    assert!(reports[1].result.is_err());
+    assert!(reports[2].output_size().is_some_and(|size| size > 0));
+    assert!(progress_events.load(Ordering::SeqCst) > 0);
+}
+
+/// Tests detecting the format of documents without loading them
+#[test]
+fn test_detect_format() {
+    let office = shared_office();
+
+    let input_url = DocUrl::from_relative_path("./tests/samples/sample-docx.docx").unwrap();
+    let format = office.detect_format(&input_url).unwrap();
+    assert_eq!(format.document_type, DocumentType::Text);
+    assert_eq!(format.filter_name, "MS Word 2007 XML");
+
+    let input_url =
+        DocUrl::from_relative_path("./tests/samples/sample-xlsx-encrypted.xlsx").unwrap();
+    let format = office.detect_format(&input_url).unwrap();
+    assert_eq!(format.document_type, DocumentType::Spreadsheet);
+    assert!(format.encrypted);
+}
+
+/// Tests loading a missing document reports a not found error
+#[test]
+fn test_document_load_missing_file() {
+    let office = shared_office();
+
+    let input_url = DocUrl::from_absolute_path("/tmp/libreofficekit-missing.docx").unwrap();
+    let err = office.document_load(&input_url).err().unwrap();
+
+    match err {
+        OfficeError::FileNotFound { url, .. } => assert_eq!(url, Some(input_url)),
+        err => panic!("unexpected error {err:?}"),
+    }
+}
+
+/// Tests saving with an unknown format reports the failure
+#[test]
+fn test_save_as_invalid_filter() {
+    let office = shared_office();
+
+    let input_url = DocUrl::from_relative_path("./tests/samples/sample-docx.docx").unwrap();
+    let output_url = DocUrl::from_absolute_path("/tmp/test-invalid-filter.out").unwrap();
+
+    let mut document = office.document_load(&input_url).unwrap();
+    let err = document
+        .save_as(&output_url, "not-a-format", None)
+        .unwrap_err();
+
+    match err {
+        OfficeError::SaveFailed { url, format, .. } => {
+            assert_eq!(url, output_url);
+            assert_eq!(format, "not-a-format");
+        }
+        err => panic!("unexpected error {err:?}"),
+    }
+}
+
+/// Tests saving to a path that cannot be written reports the reason
+#[test]
+fn test_save_as_unwritable_path() {
+    let office = shared_office();
+
+    let input_url = DocUrl::from_relative_path("./tests/samples/sample-docx.docx").unwrap();
+    let output_url =
+        DocUrl::from_absolute_path("/libreofficekit-missing-directory/test.pdf").unwrap();
+
+    let mut document = office.document_load(&input_url).unwrap();
+    let err = document.save_as(&output_url, "pdf", None).unwrap_err();
+
+    match err {
+        OfficeError::SaveFailed { reason, .. } => assert!(!reason.is_empty()),
+        err => panic!("unexpected error {err:?}"),
+    }
+}
+
+/// Tests loading a missing document with options reports an error
+/// instead of returning an invalid document
+#[test]
+fn test_document_load_with_options_missing_file() {
+    let office = shared_office();
+
+    let input_url = DocUrl::from_absolute_path("/tmp/libreofficekit-missing.docx").unwrap();
+    let err = office
+        .document_load_with_options(&input_url, &LoadOptions::new().batch(true))
+        .err()
+        .unwrap();
+
+    assert!(matches!(
+        err,
+        OfficeError::FileNotFound { .. } | OfficeError::LoadFailed { .. }
+    ));
+}
+
+/// Tests strict font conversions only succeed when no fonts are missing
+#[test]
+fn test_convert_strict_fonts() {
+    let office = shared_office();
+
+    let output = Path::new("/tmp/test-convert-strict-fonts.pdf");
+    let result = office.convert(
+        Path::new("./tests/samples/sample-docx.docx"),
+        output,
+        ConvertOptions::new().strict_fonts(true),
+    );
+
+    match result {
+        Ok(value) => assert!(value.missing_fonts.is_empty()),
+        Err(OfficeError::FontsMissing(fonts)) => {
+            assert!(!fonts.is_empty());
+            assert!(!output.exists());
+        }
+        Err(err) => panic!("unexpected error: {err}"),
+    }
+}
+
+/// Tests fonts missing from a document are reported by conversions
+#[test]
+fn test_convert_missing_fonts() {
+    let office = shared_office();
+
+    let output = office
+        .convert(
+            Path::new("./tests/samples/sample-missing-font.fodt"),
+            Path::new("/tmp/test-convert-missing-fonts.pdf"),
+            ConvertOptions::new(),
+        )
+        .unwrap();
+
+    assert!(output
+        .missing_fonts
+        .contains(&"Libreofficekit Missing Font".to_string()));
+}
+
+/// Tests strict font conversions fail for documents using missing fonts
+#[test]
+fn test_convert_strict_fonts_missing() {
+    let office = shared_office();
+
+    let output = Path::new("/tmp/test-convert-strict-fonts-missing.pdf");
+    let err = office
+        .convert(
+            Path::new("./tests/samples/sample-missing-font.fodt"),
+            output,
+            ConvertOptions::new().strict_fonts(true),
+        )
+        .unwrap_err();
+
+    match err {
+        OfficeError::FontsMissing(fonts) => {
+            assert!(fonts.contains(&"Libreofficekit Missing Font".to_string()));
+        }
+        err => panic!("unexpected error: {err}"),
+    }
+
+    assert!(!output.exists());
+}
+
+/// Tests loading with a modify password keeps the optional features set by the caller
+#[test]
+fn test_sample_docx_modify_password_keeps_features() {
+    let office = shared_office();
+
+    office
+        .set_optional_features(OfficeOptionalFeatures::DOCUMENT_PASSWORD)
+        .unwrap();
+
+    let input_url =
+        DocUrl::from_relative_path("./tests/samples/sample-docx-modify-password.docx").unwrap();
+
+    office
+        .document_load_with_modify_password(&input_url, Some("password"))
+        .unwrap();
+
+    assert_eq!(
+        office.optional_features(),
+        OfficeOptionalFeatures::DOCUMENT_PASSWORD
+    );
 }
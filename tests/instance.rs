@@ -1,34 +1,56 @@
-use libreofficekit::Office;
-use parking_lot::Mutex;
+use std::path::Path;
 
-/// Mutex to prevent parallel test runs
-static TEST_MUTEX: Mutex<()> = Mutex::new(());
+use libreofficekit::{
+    DocUrl, DocumentType, FontSubstitutions, InstallPolicy, Office, OfficeError, SalLogLevel,
+    SalLogOverride, SharedOffice,
+};
+
+/// Obtains the shared office instance
+fn shared_office() -> SharedOffice {
+    // SAFETY: Documents and instances created by each test are dropped before the guard
+    unsafe { Office::shared() }.expect("failed to create office instance")
+}
 
 /// Tests that an office instance can be found
 #[test]
 fn test_find_office_instance() {
-    let _lock = TEST_MUTEX.lock();
-
     let office_path = Office::find_install_path();
     office_path.expect("missing office install path");
 }
 
+/// Tests that discovered installs include the found install
+#[test]
+fn test_discover_installs() {
+    let office_path = Office::find_install_path().expect("missing office install path");
+    let office_path = std::fs::canonicalize(office_path).unwrap();
+
+    let installs = Office::discover_installs();
+    assert!(installs.iter().any(|install| install.path == office_path));
+
+    let install = Office::find_install(InstallPolicy::Latest).expect("missing install");
+    assert!(installs.contains(&install));
+}
+
 /// Tests that an office instance can be created
 #[test]
 fn test_create_office_instance() {
-    let _lock = TEST_MUTEX.lock();
+    let _office = shared_office();
+}
+
+/// Tests that another instance cannot be created while the shared instance exists
+#[test]
+fn test_create_second_office_instance() {
+    let _office = shared_office();
 
     let office_path = Office::find_install_path().expect("missing office install path");
-    let _office = Office::new(office_path).expect("failed to create office instance");
+    let err = Office::new(office_path).err().unwrap();
+    assert!(matches!(err, OfficeError::InstanceLock));
 }
 
 /// Tests obtaining the LibreOffice version information
 #[test]
 fn test_version_info() {
-    let _lock = TEST_MUTEX.lock();
-
-    let office_path = Office::find_install_path().expect("missing office install path");
-    let office = Office::new(office_path).expect("failed to create office instance");
+    let office = shared_office();
     let version_info = office.get_version_info().unwrap();
 
     dbg!(version_info);
@@ -37,11 +59,128 @@ fn test_version_info() {
 /// Tests obtaining the available filter types
 #[test]
 fn test_filter_types() {
-    let _lock = TEST_MUTEX.lock();
-
-    let office_path = Office::find_install_path().expect("missing office install path");
-    let office = Office::new(office_path).expect("failed to create office instance");
+    let office = shared_office();
     let filter_types = office.get_filter_types().unwrap();
 
     dbg!(filter_types);
 }
+
+/// Tests obtaining the filter catalog
+#[test]
+fn test_filter_catalog() {
+    let office = shared_office();
+    let catalog = office.get_filter_catalog().unwrap();
+
+    let filter = catalog
+        .export_filter_for(DocumentType::Text, "pdf")
+        .expect("missing pdf export filter");
+    assert_eq!(filter.name, "writer_pdf_Export");
+
+    assert!(!catalog
+        .import_filters_for(
+            "application/vnd.openxmlformats-officedocument.wordprocessingml.document"
+        )
+        .is_empty());
+}
+
+/// Tests setting the typed office options
+#[test]
+fn test_typed_options() {
+    let office = shared_office();
+
+    let log_override = SalLogOverride::new()
+        .enable(SalLogLevel::Warn, None)
+        .unwrap();
+    office.set_sal_log_override(&log_override).unwrap();
+    office.set_sal_log_override(&SalLogOverride::new()).unwrap();
+
+    office.set_language("en-US").unwrap();
+    assert!(matches!(
+        office.set_language("not a language"),
+        Err(OfficeError::InvalidOptionValue { .. })
+    ));
+}
+
+/// Tests capturing stderr while loading documents
+#[cfg(target_os = "linux")]
+#[test]
+fn test_stderr_capture() {
+    let office = shared_office();
+
+    office.set_stderr_capture(true).unwrap();
+
+    let input_url = DocUrl::from_relative_path("./tests/samples/sample-docx.docx").unwrap();
+    let _document = office.document_load(&input_url).unwrap();
+
+    // Lines are only taken once
+    _ = office.take_stderr();
+    assert!(office.take_stderr().is_empty());
+
+    office.set_stderr_capture(false).unwrap();
+}
+
+/// Tests recording a performance trace while converting a document
+#[test]
+fn test_trace() {
+    let office = shared_office();
+
+    assert!(matches!(
+        office.stop_trace(),
+        Err(OfficeError::TraceNotStarted)
+    ));
+
+    office.start_trace().unwrap();
+
+    let input_url = DocUrl::from_relative_path("./tests/samples/sample-docx.docx").unwrap();
+    let mut document = office.document_load(&input_url).unwrap();
+
+    let output_url = DocUrl::from_absolute_path("/tmp/test-trace.pdf").unwrap();
+    document.save_as(&output_url, "pdf", None).unwrap();
+
+    let trace = office.stop_trace().unwrap();
+
+    // Loading and saving the document records events
+    assert!(!trace.is_empty());
+
+    let trace_path = Path::new("/tmp/test-trace.json");
+    trace.write_to(trace_path).unwrap();
+
+    let value: serde_json::Value =
+        serde_json::from_slice(&std::fs::read(trace_path).unwrap()).unwrap();
+    assert_eq!(
+        value["traceEvents"].as_array().map(Vec::len),
+        Some(trace.events().len())
+    );
+}
+
+/// Tests adding fonts from a directory
+#[test]
+fn test_add_font_directory() {
+    let office = shared_office();
+
+    let directory = tempfile::tempdir().unwrap();
+    assert!(office
+        .add_font_directory(directory.path())
+        .unwrap()
+        .is_empty());
+
+    assert!(matches!(
+        office.add_font_directory(Path::new("./tests/samples/sample-docx.docx")),
+        Err(OfficeError::InvalidPath)
+    ));
+}
+
+/// Tests that an instance with font substitutions cannot be created while
+/// another instance exists
+#[test]
+fn test_font_substitutions_instance_lock() {
+    let _office = shared_office();
+    let office_path = Office::find_install_path().expect("missing office install path");
+
+    let substitutions = FontSubstitutions::new().replace("Calibri", "Carlito");
+
+    assert!(matches!(
+        Office::new_with_font_substitutions(office_path, &substitutions),
+        Err(OfficeError::InstanceLock)
+    ));
+}
@@ -0,0 +1,14 @@
+use libreofficekit::{Office, OfficeError};
+
+/// Tests that LibreOffice cannot be initialized again once the instance
+/// is dropped, runs in its own process as the instance is not shared
+#[test]
+fn test_reinit_unsupported() {
+    let office_path = Office::find_install_path().expect("missing office install path");
+
+    let office = Office::new(&office_path).expect("failed to create office instance");
+    drop(office);
+
+    let err = Office::new(&office_path).err().unwrap();
+    assert!(matches!(err, OfficeError::ReinitUnsupported));
+}